@@ -1,55 +1,311 @@
 //! Core analysis functionality for detecting zero address validation patterns
 
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
-
-use regex::{Regex, RegexBuilder};
-use walkdir::WalkDir;
-
-use crate::types::{AnalysisResult, ConstructorAnalyzerError, FunctionType, ValidationType};
+use std::ops::{ControlFlow, Range};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+use regex::{Captures, Match, Regex, RegexBuilder};
+
+use crate::fingerprint;
+use crate::natspec;
+use crate::severity::{Severity, SeverityRule};
+use crate::suppression::Suppression;
+use crate::types::{
+    AnalysisResult, ConstructorAnalyzerError, FileError, FileOrigin, Finding, FunctionType,
+    ParameterSpan, ParameterUsage, PathAnalysis, SourceExcerpt, StateMutability,
+    ValidationEvidence, ValidationType, Visibility,
+};
 
 /// Main analyzer struct
 pub struct ConstructorAnalyzer {
     constructor_regex: Regex,
     initialize_regex: Regex,
     function_regex: Regex,
+    modifier_regex: Regex,
+    special_function_regex: Regex,
+    container_regex: Regex,
+    immutable_regex: Regex,
     address_regex: Regex,
     equality_regex: Regex,
     require_regex: Regex,
+    access_control_modifiers: Vec<String>,
+    caller_check_regexes: Vec<Regex>,
+    severity_overrides: HashMap<SeverityRule, Severity>,
+    source_context: Option<usize>,
+    validator_regexes: Vec<Regex>,
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+    use_ignore_files: bool,
+    skip_dependencies: bool,
+    fingerprint_root: Option<PathBuf>,
+}
+
+/// Options that tune how functions are analyzed
+#[derive(Debug, Clone)]
+pub struct AnalyzerOptions {
+    /// Modifier names that restrict who may call a function, matched without arguments
+    pub access_control_modifiers: Vec<String>,
+    /// Regex patterns that, when found in a function body, restrict who may call it
+    pub caller_checks: Vec<String>,
+    /// Severities that replace the default of a rule
+    pub severity_overrides: HashMap<SeverityRule, Severity>,
+    /// Attach the original source to each result, with this many lines of context
+    pub source_context: Option<usize>,
+    /// Functions that reject address(0) among their arguments, e.g. `_requireNonZero`;
+    /// passing a parameter to one of them validates it
+    pub validators: Vec<String>,
+    /// Globs a file must match, relative to the analyzed directory or absolute; empty
+    /// includes every file
+    pub include: Vec<String>,
+    /// Globs of files and directories to skip, relative to the analyzed directory or absolute
+    pub exclude: Vec<String>,
+    /// Skip files matched by `.gitignore`, `.ignore` and `.construstorignore` files
+    pub use_ignore_files: bool,
+    /// Skip dependency and test directories (see [`DEPENDENCY_DIRECTORIES`])
+    pub skip_dependencies: bool,
+    /// Directory that file paths are made relative to for finding fingerprints, e.g. the
+    /// directory of the configuration file; defaults to the top of the git repository, or
+    /// else the working directory
+    pub fingerprint_root: Option<PathBuf>,
+}
+
+/// Name of the tool-specific ignore file, in `.gitignore` syntax
+pub const IGNORE_FILE_NAME: &str = ".construstorignore";
+
+/// Directories skipped by default: `node_modules` anywhere, the others at the root of the
+/// analyzed directory
+pub const DEPENDENCY_DIRECTORIES: [&str; 4] = ["node_modules", "lib", "test", "tests"];
+
+impl Default for AnalyzerOptions {
+    fn default() -> Self {
+        Self {
+            access_control_modifiers: [
+                "onlyOwner",
+                "onlyRole",
+                "onlyAdmin",
+                "onlyGovernance",
+                "onlyGovernor",
+                "onlyAuthorized",
+                "onlyOperator",
+                "onlyProxyAdmin",
+                "auth",
+                "requiresAuth",
+                "restricted",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            // msg.sender compared with an owner- or admin-like variable or getter, or
+            // checked for a role
+            caller_checks: [
+                r"\bmsg\.sender\s*(?:==|!=)\s*(?:\w+\.)*(?i:\w*(?:owner|admin|gov|guardian|operator|authority)\w*)\b(?:\s*\(\s*\))?",
+                r"\b(?i:\w*(?:owner|admin|gov|guardian|operator|authority)\w*)(?:\s*\(\s*\))?\s*(?:==|!=)\s*msg\.sender\b",
+                r"\b_checkOwner\s*\(",
+                r"\b_checkRole\s*\(",
+                r"\bhasRole\s*\([^;{}]*?\bmsg\.sender\s*\)",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            severity_overrides: HashMap::new(),
+            source_context: None,
+            validators: Vec::new(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+            use_ignore_files: true,
+            skip_dependencies: true,
+            fingerprint_root: None,
+        }
+    }
+}
+
+/// Kind of top-level definition that can enclose functions
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ContainerKind {
+    Contract,
+    Library,
+    Interface,
+}
+
+/// A contract, library or interface body located in a source file
+#[derive(Debug)]
+struct Container {
+    kind: ContainerKind,
+    name: String,
+    /// Byte offset of the opening brace
+    start: usize,
+    /// Byte offset of the closing brace
+    end: usize,
+    /// Names of the immutable state variables declared in the body
+    immutables: Vec<String>,
+}
+
+/// A function-like definition located in a source file, before analysis
+struct Definition<'a> {
+    function_type: FunctionType,
+    arguments: &'a str,
+    /// Text between the parameter list and the opening brace
+    attributes: &'a str,
+    body: &'a str,
+    /// Body with comments and string literals blanked out
+    masked_body: &'a str,
+    /// Byte range of the definition in the file, from its keyword to its closing brace
+    span: Range<usize>,
+    /// Byte offset of the parameter list in the file
+    arguments_offset: usize,
+    /// Line and column where the definition starts in the file
+    position: (usize, usize),
+    /// Line and column where the parameter list starts in the file
+    arguments_position: (usize, usize),
+    /// Line and column where the body starts in the file
+    body_position: (usize, usize),
+    contract_name: Option<String>,
+    /// Immutable state variables of the enclosing contract
+    immutables: Vec<String>,
+}
+
+impl<'a> Definition<'a> {
+    /// Builds a definition from a header match ending at the opening brace
+    ///
+    /// `groups` holds the capture indices of the parameter list and the attributes.
+    fn from_captures(
+        function_type: FunctionType,
+        (contents, masked): (&'a str, &'a str),
+        captures: &Captures,
+        (arguments_group, attributes_group): (usize, usize),
+        containers: &[Container],
+    ) -> Option<Self> {
+        let container = enclosing_container(containers, captures.get(0));
+        let span = body_span(contents, masked, captures)?;
+        let start = captures.get(0)?.start();
+        let close = find_matching_brace(masked, captures.get(0)?.end() - 1)?;
+        let arguments_offset = captures.get(arguments_group).map_or(span.start, |m| {
+            let text = &contents[m.range()];
+            m.start() + (text.len() - text.trim_start().len())
+        });
+        Some(Self {
+            function_type,
+            arguments: capture_text(contents, captures, arguments_group),
+            attributes: capture_text(contents, captures, attributes_group),
+            body: &contents[span.clone()],
+            masked_body: &masked[span.clone()],
+            span: start..close + 1,
+            arguments_offset,
+            position: line_column(contents, start),
+            arguments_position: line_column(contents, arguments_offset),
+            body_position: line_column(contents, span.start),
+            contract_name: container.map(|container| container.name.clone()),
+            immutables: container
+                .map(|container| container.immutables.clone())
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// Visibility, mutability and modifier invocations parsed from a function header
+#[derive(Debug, Default, PartialEq)]
+struct HeaderAttributes {
+    visibility: Option<Visibility>,
+    mutability: StateMutability,
+    modifiers: Vec<String>,
 }
 
 impl ConstructorAnalyzer {
     /// Creates a new ConstructorAnalyzer instance
     pub fn new() -> Result<Self, ConstructorAnalyzerError> {
-        let constructor_regex = RegexBuilder::new(r"constructor\s*\((.*?)\)\s*\{(.*?)\}")
+        Self::with_options(AnalyzerOptions::default())
+    }
+
+    /// Creates a new ConstructorAnalyzer instance with custom options
+    pub fn with_options(options: AnalyzerOptions) -> Result<Self, ConstructorAnalyzerError> {
+        // Header regexes stop at the opening brace; bodies are found by brace matching
+        let constructor_regex = RegexBuilder::new(r"\bconstructor\s*\(([^;{}]*?)\)([^{;]*)\{")
             .multi_line(true)
             .dot_matches_new_line(true)
             .build()?;
 
         let initialize_regex =
-            RegexBuilder::new(r"function\s+initialize\s*\((.*?)\)\s*[^{]*\{(.*?)\}")
+            RegexBuilder::new(r"\bfunction\s+initialize\s*\(([^;{}]*?)\)([^{;]*)\{")
                 .multi_line(true)
                 .dot_matches_new_line(true)
                 .build()?;
 
         // Regex to match all functions (excluding constructor and initialize)
-        let function_regex = RegexBuilder::new(r"function\s+(\w+)\s*\((.*?)\)\s*[^{]*\{(.*?)\}")
+        let function_regex = RegexBuilder::new(r"\bfunction\s+(\w+)\s*\(([^;{}]*?)\)([^{;]*)\{")
             .multi_line(true)
             .dot_matches_new_line(true)
             .build()?;
 
-        let address_regex =
-            Regex::new(r"(address(?:\[\])?(?:\s+memory|\s+storage|\s+calldata)?)\s+(\w+)")?;
+        // Modifiers may omit the parameter list entirely
+        let modifier_regex =
+            RegexBuilder::new(r"\bmodifier\s+(\w+)\s*(?:\(([^;{}]*?)\))?([^{;]*)\{")
+                .multi_line(true)
+                .dot_matches_new_line(true)
+                .build()?;
+
+        // `fallback` and `receive` are declared without the `function` keyword
+        let special_function_regex =
+            RegexBuilder::new(r"\b(fallback|receive)\s*\(([^;{}]*?)\)([^{;]*)\{")
+                .multi_line(true)
+                .dot_matches_new_line(true)
+                .build()?;
+
+        let container_regex =
+            Regex::new(r"\b(?:abstract\s+)?(contract|library|interface)\s+(\w+)[^{;]*\{")?;
+
+        let immutable_regex = Regex::new(r"\bimmutable\b[^;=(){}]*?\b(\w+)\s*[;=]")?;
+
+        let address_regex = Regex::new(
+            r"(address(?:\s+payable)?(?:\[\])?(?:\s+memory|\s+storage|\s+calldata)?)\s+(\w+)",
+        )?;
         let equality_regex = Regex::new(r"(\w+)\s*(?:==|!=)\s*address\(0\)")?;
         let require_regex = Regex::new(r"(?:require)\s*\(\s*([^,)]+)\s*(?:==|!=)\s*address\(0\)")?;
 
+        let caller_check_regexes = options
+            .caller_checks
+            .iter()
+            .map(|pattern| Regex::new(pattern))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // A validator call covers every plain identifier passed to it
+        let validator_regexes = options
+            .validators
+            .iter()
+            .map(|name| Regex::new(&format!(r"\b{}\s*\(([^;{{}}]*)\)", regex::escape(name))))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let include = if options.include.is_empty() {
+            None
+        } else {
+            Some(build_glob_set(&options.include)?)
+        };
+        let exclude = build_glob_set(&options.exclude)?;
+
         Ok(Self {
             constructor_regex,
             initialize_regex,
             function_regex,
+            modifier_regex,
+            special_function_regex,
+            container_regex,
+            immutable_regex,
             address_regex,
             equality_regex,
             require_regex,
+            access_control_modifiers: options.access_control_modifiers,
+            caller_check_regexes,
+            severity_overrides: options.severity_overrides,
+            source_context: options.source_context,
+            validator_regexes,
+            include,
+            exclude,
+            use_ignore_files: options.use_ignore_files,
+            skip_dependencies: options.skip_dependencies,
+            fingerprint_root: options.fingerprint_root,
         })
     }
 
@@ -59,31 +315,170 @@ impl ConstructorAnalyzer {
         path: &str,
         all_functions: bool,
     ) -> Result<Vec<AnalysisResult>, ConstructorAnalyzerError> {
-        let path = Path::new(path);
+        let mut results = Vec::new();
+        let mut failure = None;
+        self.analyze_path_streaming(path, all_functions, |_, outcome| match outcome {
+            Ok(file_results) => {
+                results.extend(file_results);
+                ControlFlow::Continue(())
+            }
+            Err(err) => {
+                failure = Some(err);
+                ControlFlow::Break(())
+            }
+        })?;
+
+        match failure {
+            Some(err) => Err(err),
+            None => Ok(results),
+        }
+    }
 
+    /// Analyzes a file or directory, recording files that cannot be analyzed instead of stopping
+    ///
+    /// Only a missing `path` is an error.
+    pub fn analyze_tree(
+        &self,
+        path: &str,
+        all_functions: bool,
+    ) -> Result<PathAnalysis, ConstructorAnalyzerError> {
+        let mut analysis = PathAnalysis::default();
+        self.analyze_path_streaming(path, all_functions, |file, outcome| {
+            match outcome {
+                Ok(file_results) => analysis.results.extend(file_results),
+                Err(err) => analysis.errors.push(FileError {
+                    path: file.to_string_lossy().to_string(),
+                    message: err.to_string(),
+                }),
+            }
+            ControlFlow::Continue(())
+        })?;
+
+        Ok(analysis)
+    }
+
+    /// Analyzes a file or directory one file at a time, handing the outcome of each file to
+    /// `on_file` as soon as it is known
+    ///
+    /// Nothing is retained between files, so memory use does not grow with the tree.
+    /// Returning [`ControlFlow::Break`] from `on_file` stops the walk. Only a missing `path`
+    /// is an error.
+    pub fn analyze_path_streaming<F>(
+        &self,
+        path: &str,
+        all_functions: bool,
+        mut on_file: F,
+    ) -> Result<(), ConstructorAnalyzerError>
+    where
+        F: FnMut(&Path, Result<Vec<AnalysisResult>, ConstructorAnalyzerError>) -> ControlFlow<()>,
+    {
+        let root = Path::new(path);
+        let dependency_roots = remapping_targets(root);
+        // Fingerprints use canonical paths, so that `.` and an absolute path agree, relative
+        // to a root that does not depend on which part of the project is analyzed
+        let fingerprint_root = match &self.fingerprint_root {
+            Some(directory) => fs::canonicalize(directory).ok(),
+            None => repository_root(root)
+                .or_else(|| std::env::current_dir().and_then(fs::canonicalize).ok()),
+        };
+        for file in self.solidity_files(root)? {
+            let relative = match file.strip_prefix(root) {
+                Ok(relative) if !relative.as_os_str().is_empty() => relative,
+                _ => &file,
+            };
+            let origin = FileOrigin::classify(relative, &dependency_roots);
+            let fingerprint_path = fingerprint_root
+                .as_deref()
+                .zip(fs::canonicalize(&file).ok())
+                .and_then(|(directory, canonical)| {
+                    Some(
+                        canonical
+                            .strip_prefix(directory)
+                            .ok()?
+                            .to_string_lossy()
+                            .to_string(),
+                    )
+                })
+                .unwrap_or_else(|| file.to_string_lossy().to_string());
+            let outcome = self
+                .analyze_file_at(&file, &fingerprint_path, all_functions)
+                .map(|mut results| {
+                    for result in &mut results {
+                        result.origin = origin;
+                    }
+                    results
+                });
+            if on_file(&file, outcome).is_break() {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Lists the Solidity files to analyze, lazily: `path` itself, or every `.sol` file
+    /// below it that is not ignored and passes the include and exclude globs
+    fn solidity_files<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<impl Iterator<Item = PathBuf> + 'a, ConstructorAnalyzerError> {
         if !path.exists() {
             return Err(ConstructorAnalyzerError::NotFound(
                 path.to_string_lossy().to_string(),
             ));
         }
 
-        let mut results = Vec::new();
+        // Excluded and dependency directories are pruned instead of walked. Absolute globs,
+        // such as those of a configuration file, are matched against the resolved path.
+        let root = path.to_path_buf();
+        let absolute_root = fs::canonicalize(path).unwrap_or_else(|_| root.clone());
+        let prune_root = absolute_root.clone();
+        let exclude = self.exclude.clone();
+        let skip_dependencies = self.skip_dependencies;
+        let prune = move |entry: &ignore::DirEntry| {
+            let relative = entry.path().strip_prefix(&root).unwrap_or(entry.path());
+            entry.depth() == 0
+                || !entry.file_type().is_some_and(|kind| kind.is_dir())
+                || !(matches_glob(&exclude, &prune_root, relative)
+                    || skip_dependencies && is_dependency_directory(relative))
+        };
 
-        if path.is_dir() {
-            for entry in WalkDir::new(path)
-                .into_iter()
-                .filter_map(|e| e.ok())
-                .filter(|e| e.path().extension().is_some_and(|ext| ext == "sol"))
-            {
-                let file_results = self.analyze_file(entry.path(), all_functions)?;
-                results.extend(file_results);
-            }
-        } else {
-            let file_results = self.analyze_file(path, all_functions)?;
-            results.extend(file_results);
+        // A file given explicitly is analyzed whatever its extension, ignore files and globs.
+        // Only ignore files committed with the project are read, so that the user's global
+        // gitignore and `.git/info/exclude` do not make results differ between machines.
+        let mut walker = WalkBuilder::new(path);
+        walker
+            .hidden(false)
+            .require_git(false)
+            .git_ignore(self.use_ignore_files)
+            .git_exclude(false)
+            .git_global(false)
+            .ignore(self.use_ignore_files)
+            .parents(self.use_ignore_files)
+            .sort_by_file_name(|a, b| a.cmp(b))
+            .filter_entry(prune);
+        if self.use_ignore_files {
+            walker.add_custom_ignore_filename(IGNORE_FILE_NAME);
         }
 
-        Ok(results)
+        Ok(walker
+            .build()
+            .filter_map(|e| e.ok())
+            .filter(move |e| {
+                let is_file = e.file_type().is_some_and(|kind| !kind.is_dir());
+                if e.depth() == 0 {
+                    return is_file;
+                }
+                let relative = e.path().strip_prefix(path).unwrap_or(e.path());
+                is_file
+                    && e.path().extension().is_some_and(|ext| ext == "sol")
+                    && self
+                        .include
+                        .as_ref()
+                        .is_none_or(|set| matches_glob(set, &absolute_root, relative))
+                    && !matches_glob(&self.exclude, &absolute_root, relative)
+            })
+            .map(|e| e.into_path()))
     }
 
     /// Analyzes a single Solidity file
@@ -91,6 +486,16 @@ impl ConstructorAnalyzer {
         &self,
         file_path: &Path,
         all_functions: bool,
+    ) -> Result<Vec<AnalysisResult>, ConstructorAnalyzerError> {
+        self.analyze_file_at(file_path, &file_path.to_string_lossy(), all_functions)
+    }
+
+    /// Analyzes a single Solidity file, fingerprinting its findings with `fingerprint_path`
+    fn analyze_file_at(
+        &self,
+        file_path: &Path,
+        fingerprint_path: &str,
+        all_functions: bool,
     ) -> Result<Vec<AnalysisResult>, ConstructorAnalyzerError> {
         let contents = fs::read_to_string(file_path)?;
         let file_name = file_path
@@ -99,73 +504,329 @@ impl ConstructorAnalyzer {
             .to_string_lossy()
             .to_string();
 
-        let mut results = Vec::new();
+        Ok(self.analyze_source_at(
+            &contents,
+            &file_name,
+            (&file_path.to_string_lossy(), fingerprint_path),
+            all_functions,
+        ))
+    }
 
-        // Analyze constructors
-        for captures in self.constructor_regex.captures_iter(&contents) {
-            let args = captures.get(1).map_or("", |m| m.as_str()).trim();
-            let body = captures.get(2).map_or("", |m| m.as_str()).trim();
+    /// Analyzes Solidity source code that was read from `file_name`
+    pub fn analyze_source(
+        &self,
+        contents: &str,
+        file_name: &str,
+        all_functions: bool,
+    ) -> Vec<AnalysisResult> {
+        self.analyze_source_at(contents, file_name, (file_name, file_name), all_functions)
+    }
 
-            // Construct the full function definition
-            let full_function = format!("constructor({}) {{\n{}\n}}", args, body);
+    /// Analyzes Solidity source code that was read from `file_path`, named `file_name`
+    ///
+    /// Findings are fingerprinted with `fingerprint_path`, which may differ from the
+    /// reported path.
+    fn analyze_source_at(
+        &self,
+        contents: &str,
+        file_name: &str,
+        (file_path, fingerprint_path): (&str, &str),
+        all_functions: bool,
+    ) -> Vec<AnalysisResult> {
+        // Comments and string literals are blanked out so that braces and keywords
+        // inside them do not confuse matching; byte offsets stay identical.
+        let masked = mask_comments_and_strings(contents);
+        let containers = self.find_containers(&masked);
+        let suppressions = Suppression::find_all(contents, &masked);
+
+        let definitions = self.find_definitions(contents, &masked, &containers, all_functions);
+
+        definitions
+            .into_iter()
+            .filter(|definition| {
+                // Only constructors and initializers are reported without address parameters
+                matches!(
+                    definition.function_type,
+                    FunctionType::Constructor | FunctionType::Initialize
+                ) || !self
+                    .extract_address_arguments(definition.arguments)
+                    .is_empty()
+            })
+            .map(|definition| {
+                let mut result = self.analyze_definition(definition, contents, file_name);
+                result.file_path = file_path.to_string();
+                let signature = result.canonical_signature();
+                for finding in &mut result.findings {
+                    finding.fingerprint = fingerprint::finding_fingerprint(
+                        fingerprint_path,
+                        result.contract_name.as_deref(),
+                        &signature,
+                        &finding.parameter,
+                    );
+                }
+                let line = result.line;
+                result.suppressed_findings = result.take_findings(|finding| {
+                    suppressions
+                        .iter()
+                        .any(|suppression| suppression.covers(finding, line))
+                });
+                result
+            })
+            .collect()
+    }
+
+    /// Locates the definitions to analyze in a source file and its masked copy
+    fn find_definitions<'a>(
+        &self,
+        contents: &'a str,
+        masked: &'a str,
+        containers: &[Container],
+        all_functions: bool,
+    ) -> Vec<Definition<'a>> {
+        let mut definitions = Vec::new();
 
-            let result = self.analyze_function(
+        // Collect constructors
+        for captures in self.constructor_regex.captures_iter(masked) {
+            definitions.extend(Definition::from_captures(
                 FunctionType::Constructor,
-                file_name.clone(),
-                args,
-                &full_function,
-            );
-            results.push(result);
+                (contents, masked),
+                &captures,
+                (1, 2),
+                containers,
+            ));
         }
 
-        // Analyze initialize functions
-        for captures in self.initialize_regex.captures_iter(&contents) {
-            let args = captures.get(1).map_or("", |m| m.as_str()).trim();
-            let body = captures.get(2).map_or("", |m| m.as_str()).trim();
-
-            // Construct the full function definition
-            let full_function = format!("function initialize({}) {{\n{}\n}}", args, body);
-
-            let result = self.analyze_function(
+        // Collect initialize functions
+        for captures in self.initialize_regex.captures_iter(masked) {
+            definitions.extend(Definition::from_captures(
                 FunctionType::Initialize,
-                file_name.clone(),
-                args,
-                &full_function,
-            );
-            results.push(result);
+                (contents, masked),
+                &captures,
+                (1, 2),
+                containers,
+            ));
         }
 
-        // Analyze all other functions (only if all_functions flag is enabled)
+        // Collect all other functions, modifiers, fallback and receive functions (only if
+        // all_functions flag is enabled)
         if all_functions {
-            for captures in self.function_regex.captures_iter(&contents) {
-                let function_name = captures.get(1).map_or("", |m| m.as_str()).trim();
-                let args = captures.get(2).map_or("", |m| m.as_str()).trim();
-                let body = captures.get(3).map_or("", |m| m.as_str()).trim();
+            for captures in self.function_regex.captures_iter(masked) {
+                let function_name = capture_text(contents, &captures, 1).to_string();
 
-                // Skip initialize function as we've already analyzed it above
+                // Skip initialize function as we've already collected it above
                 if function_name == "initialize" {
                     continue;
                 }
 
-                // Only analyze functions that have address parameters
-                let address_arguments = self.extract_address_arguments(args);
-                if !address_arguments.is_empty() {
-                    // Construct the full function definition
-                    let full_function =
-                        format!("function {}({}) {{\n{}\n}}", function_name, args, body);
-
-                    let result = self.analyze_function(
-                        FunctionType::Regular(function_name.to_string()),
-                        file_name.clone(),
-                        args,
-                        &full_function,
-                    );
-                    results.push(result);
+                let function_type = match enclosing_container(containers, captures.get(0))
+                    .map(|container| container.kind)
+                {
+                    None => FunctionType::FreeFunction(function_name),
+                    Some(ContainerKind::Library) => FunctionType::LibraryFunction(function_name),
+                    Some(_) => FunctionType::Regular(function_name),
+                };
+
+                definitions.extend(Definition::from_captures(
+                    function_type,
+                    (contents, masked),
+                    &captures,
+                    (2, 3),
+                    containers,
+                ));
+            }
+
+            for captures in self.modifier_regex.captures_iter(masked) {
+                let modifier_name = capture_text(contents, &captures, 1).to_string();
+                definitions.extend(Definition::from_captures(
+                    FunctionType::Modifier(modifier_name),
+                    (contents, masked),
+                    &captures,
+                    (2, 3),
+                    containers,
+                ));
+            }
+
+            for captures in self.special_function_regex.captures_iter(masked) {
+                // Only declarations inside a contract, not calls or `function receive(...)`
+                let start = captures.get(0).map_or(0, |m| m.start());
+                let declaration = masked[..start]
+                    .trim_end()
+                    .chars()
+                    .next_back()
+                    .is_none_or(|c| matches!(c, ';' | '{' | '}'));
+                let in_contract = enclosing_container(containers, captures.get(0))
+                    .is_some_and(|container| container.kind == ContainerKind::Contract);
+                if !declaration || !in_contract {
+                    continue;
                 }
+
+                let function_type = match &captures[1] {
+                    "fallback" => FunctionType::Fallback,
+                    _ => FunctionType::Receive,
+                };
+                definitions.extend(Definition::from_captures(
+                    function_type,
+                    (contents, masked),
+                    &captures,
+                    (2, 3),
+                    containers,
+                ));
+            }
+        }
+
+        definitions
+    }
+
+    /// Analyzes a definition located in a source file
+    fn analyze_definition(
+        &self,
+        definition: Definition<'_>,
+        contents: &str,
+        file_name: &str,
+    ) -> AnalysisResult {
+        let Definition {
+            function_type,
+            arguments,
+            attributes,
+            body,
+            masked_body,
+            span,
+            arguments_offset,
+            position: (line, column),
+            arguments_position,
+            body_position,
+            contract_name,
+            immutables,
+        } = definition;
+
+        // Construct the full function definition
+        let full_function = match &function_type {
+            FunctionType::Constructor => format!("constructor({}) {{\n{}\n}}", arguments, body),
+            FunctionType::Modifier(name) => {
+                format!("modifier {}({}) {{\n{}\n}}", name, arguments, body)
+            }
+            FunctionType::Initialize => {
+                format!("function initialize({}) {{\n{}\n}}", arguments, body)
+            }
+            FunctionType::Fallback | FunctionType::Receive => {
+                format!("{}({}) {{\n{}\n}}", function_type.name(), arguments, body)
+            }
+            FunctionType::Regular(name)
+            | FunctionType::FreeFunction(name)
+            | FunctionType::LibraryFunction(name) => {
+                format!("function {}({}) {{\n{}\n}}", name, arguments, body)
+            }
+        };
+
+        let header = parse_header_attributes(attributes);
+        // Free functions are implicitly internal; constructors and modifiers have no visibility
+        let visibility = header.visibility.or(match function_type {
+            FunctionType::FreeFunction(_) => Some(Visibility::Internal),
+            _ => None,
+        });
+
+        let mut result = self.analyze_function(
+            function_type,
+            file_name.to_string(),
+            arguments,
+            &full_function,
+        );
+        // Evidence positions are relative to the body; make them relative to the file
+        for evidence in &mut result.validation_evidence {
+            (evidence.line, evidence.column) =
+                offset_position((evidence.line, evidence.column), body_position);
+        }
+        result.line = line;
+        result.column = column;
+        result.contract_name = contract_name;
+        result.visibility = visibility;
+        result.mutability = header.mutability;
+        result.access_controls = self.find_access_controls(&header.modifiers, masked_body);
+        result.trusted_caller = !result.access_controls.is_empty();
+        // Parameters documented as accepting address(0) are unchecked on purpose
+        let allowed =
+            natspec::zero_address_allowed(natspec::doc_comment_before(contents, span.start));
+        let (zero_address_allowed, missing_validations) = result
+            .missing_validations
+            .into_iter()
+            .partition(|parameter| allowed.contains(parameter));
+        result.zero_address_allowed = zero_address_allowed;
+        result.missing_validations = missing_validations;
+        result.findings = self.classify_findings(&result, masked_body, &immutables);
+        let mut unvalidated_parameters = Vec::new();
+        for finding in &mut result.findings {
+            let declaration = self
+                .address_regex
+                .captures_iter(arguments)
+                .filter_map(|captures| captures.get(2))
+                .find(|name| name.as_str() == finding.parameter);
+            if let Some(name) = declaration {
+                (finding.line, finding.column) =
+                    offset_position(line_column(arguments, name.start()), arguments_position);
+                let start = arguments_offset + name.start() - span.start;
+                unvalidated_parameters.push(ParameterSpan {
+                    parameter: finding.parameter.clone(),
+                    start,
+                    end: start + name.len(),
+                });
             }
         }
+        result.source = self.source_context.map(|context_lines| {
+            source_excerpt(contents, span, context_lines, unvalidated_parameters)
+        });
+        result.modifiers = header.modifiers;
+        result
+    }
+
+    /// Lists the modifiers and caller checks that restrict who may call a function
+    fn find_access_controls(&self, modifiers: &[String], body: &str) -> Vec<String> {
+        let mut controls: Vec<String> = modifiers
+            .iter()
+            .filter(|invocation| {
+                let name = invocation.split('(').next().unwrap_or_default();
+                self.access_control_modifiers
+                    .iter()
+                    .any(|modifier| modifier == name)
+            })
+            .cloned()
+            .collect();
+
+        for regex in &self.caller_check_regexes {
+            if let Some(m) = regex.find(body) {
+                controls.push(m.as_str().trim().to_string());
+            }
+        }
+
+        controls
+    }
 
-        Ok(results)
+    /// Locates every contract, library and interface body in masked source
+    fn find_containers(&self, masked: &str) -> Vec<Container> {
+        self.container_regex
+            .captures_iter(masked)
+            .filter_map(|captures| {
+                let whole = captures.get(0)?;
+                let kind = match captures.get(1)?.as_str() {
+                    "library" => ContainerKind::Library,
+                    "interface" => ContainerKind::Interface,
+                    _ => ContainerKind::Contract,
+                };
+                let start = whole.end() - 1;
+                let end = find_matching_brace(masked, start)?;
+                let immutables = self
+                    .immutable_regex
+                    .captures_iter(&masked[start..end])
+                    .filter_map(|captures| captures.get(1).map(|m| m.as_str().to_string()))
+                    .collect();
+                Some(Container {
+                    kind,
+                    name: captures.get(2)?.as_str().to_string(),
+                    start,
+                    end,
+                    immutables,
+                })
+            })
+            .collect()
     }
 
     /// Analyzes a single function for zero address validation
@@ -190,11 +851,18 @@ impl ConstructorAnalyzer {
             full_function_code
         };
 
+        let validation_evidence = self.extract_validation_evidence(function_body);
         let equality_vars = self.extract_equality_checked_variables(function_body);
         let require_vars = self.extract_require_checked_variables(function_body);
 
+        let validator_vars: Vec<String> = self
+            .validator_calls(function_body)
+            .into_iter()
+            .map(|(_, variable)| variable.to_string())
+            .collect();
+
         let mut validated_variables = equality_vars.clone();
-        for var in require_vars.iter() {
+        for var in require_vars.iter().chain(&validator_vars) {
             if !validated_variables.contains(var) {
                 validated_variables.push(var.clone());
             }
@@ -213,19 +881,100 @@ impl ConstructorAnalyzer {
         if !require_vars.is_empty() {
             validation_types.push(ValidationType::RequireStatement);
         }
+        if !validator_vars.is_empty() {
+            validation_types.push(ValidationType::ValidatorCall);
+        }
 
         AnalysisResult {
             function_type,
+            file_path: file_name.clone(),
             file_name,
+            line: 1,
+            column: 1,
+            contract_name: None,
+            visibility: None,
+            mutability: StateMutability::NonPayable,
+            modifiers: Vec::new(),
+            trusted_caller: false,
+            access_controls: Vec::new(),
             arguments: arguments.to_string(),
             code: full_function_code.to_string(),
             address_arguments,
             validated_variables,
+            validation_evidence,
             missing_validations,
+            zero_address_allowed: Vec::new(),
             validation_types,
+            findings: Vec::new(),
+            suppressed_findings: Vec::new(),
+            source: None,
+            origin: FileOrigin::default(),
         }
     }
 
+    /// Assigns a usage and a severity to each missing validation
+    ///
+    /// `body` is the masked function body and `immutables` the immutable state variables
+    /// of the enclosing contract.
+    fn classify_findings(
+        &self,
+        result: &AnalysisResult,
+        body: &str,
+        immutables: &[String],
+    ) -> Vec<Finding> {
+        let initializer = matches!(
+            result.function_type,
+            FunctionType::Constructor | FunctionType::Initialize
+        );
+        let parameters: Vec<&str> = result
+            .address_arguments
+            .iter()
+            .map(|(_, name)| name.as_str())
+            .collect();
+
+        result
+            .missing_validations
+            .iter()
+            .map(|parameter| {
+                let usage = classify_usage(body, parameter, &parameters, immutables);
+                let uses = |expected: &[ParameterUsage]| usage.iter().any(|u| expected.contains(u));
+                let stored = uses(&[
+                    ParameterUsage::StoredToState,
+                    ParameterUsage::StoredToImmutable,
+                ]);
+
+                let rule = SeverityRule::ALL
+                    .into_iter()
+                    .find(|rule| match rule {
+                        SeverityRule::DelegatecallTarget => {
+                            uses(&[ParameterUsage::DelegatecallTarget])
+                        }
+                        SeverityRule::CallTarget => uses(&[ParameterUsage::CallTarget]),
+                        SeverityRule::InitializerState => stored && initializer,
+                        SeverityRule::AccessControlledSetter => stored && result.trusted_caller,
+                        SeverityRule::TokenRecipient => uses(&[ParameterUsage::TransferRecipient]),
+                        SeverityRule::StateWrite => stored,
+                        SeverityRule::Unclassified => true,
+                    })
+                    .unwrap_or(SeverityRule::Unclassified);
+
+                Finding {
+                    fingerprint: String::new(),
+                    parameter: parameter.clone(),
+                    line: result.line,
+                    column: result.column,
+                    severity: self
+                        .severity_overrides
+                        .get(&rule)
+                        .copied()
+                        .unwrap_or_else(|| rule.default_severity()),
+                    rule,
+                    usage,
+                }
+            })
+            .collect()
+    }
+
     /// Extracts address arguments from function parameters
     fn extract_address_arguments(&self, args: &str) -> Vec<(String, String)> {
         self.address_regex
@@ -243,6 +992,93 @@ impl ConstructorAnalyzer {
             .collect()
     }
 
+    /// Records where and how each variable is checked against address(0)
+    ///
+    /// Comparisons inside a `require()` are reported once, as the require statement.
+    fn extract_validation_evidence(&self, code: &str) -> Vec<ValidationEvidence> {
+        let mut matches: Vec<(usize, ValidationType, String, &str)> = Vec::new();
+        let mut require_spans = Vec::new();
+
+        for captures in self.require_regex.captures_iter(code) {
+            if let (Some(whole), Some(var_match)) = (captures.get(0), captures.get(1)) {
+                // Report the whole statement, up to its terminating semicolon
+                let end = code[whole.end()..]
+                    .find(';')
+                    .map_or(whole.end(), |semicolon| whole.end() + semicolon);
+                require_spans.push(whole.range());
+                matches.push((
+                    whole.start(),
+                    ValidationType::RequireStatement,
+                    var_match.as_str().trim().to_string(),
+                    &code[whole.start()..end],
+                ));
+            }
+        }
+
+        for captures in self.equality_regex.captures_iter(code) {
+            if let (Some(whole), Some(var_match)) = (captures.get(0), captures.get(1)) {
+                if require_spans
+                    .iter()
+                    .any(|span| span.contains(&whole.start()))
+                {
+                    continue;
+                }
+                matches.push((
+                    whole.start(),
+                    ValidationType::EqualityCheck,
+                    var_match.as_str().to_string(),
+                    whole.as_str(),
+                ));
+            }
+        }
+
+        for (whole, variable) in self.validator_calls(code) {
+            matches.push((
+                whole.start(),
+                ValidationType::ValidatorCall,
+                variable.to_string(),
+                whole.as_str(),
+            ));
+        }
+
+        matches.sort_by_key(|(offset, ..)| *offset);
+        matches
+            .into_iter()
+            .map(|(offset, kind, variable, source)| {
+                let (line, column) = line_column(code, offset);
+                ValidationEvidence {
+                    variable,
+                    kind,
+                    source: source.to_string(),
+                    line,
+                    column,
+                }
+            })
+            .collect()
+    }
+
+    /// Finds calls to the configured validators, with each plain identifier passed to them
+    fn validator_calls<'a>(&self, code: &'a str) -> Vec<(Match<'a>, &'a str)> {
+        let mut calls = Vec::new();
+        for regex in &self.validator_regexes {
+            for captures in regex.captures_iter(code) {
+                if let (Some(whole), Some(args)) = (captures.get(0), captures.get(1)) {
+                    calls.extend(
+                        args.as_str()
+                            .split(',')
+                            .map(str::trim)
+                            .filter(|arg| {
+                                !arg.is_empty()
+                                    && arg.chars().all(|c| c.is_alphanumeric() || c == '_')
+                            })
+                            .map(|arg| (whole, arg)),
+                    );
+                }
+            }
+        }
+        calls
+    }
+
     /// Extracts variables checked with equality operators
     fn extract_equality_checked_variables(&self, code: &str) -> Vec<String> {
         let mut variables = Vec::new();
@@ -276,9 +1112,469 @@ impl ConstructorAnalyzer {
     }
 }
 
+/// Lists the dependency directories that import remappings point to, relative to `root`,
+/// from its `remappings.txt` and the profiles of its `foundry.toml`
+///
+/// Targets inside the project's own source, test or script directories, such as the
+/// self-alias `@app/=src/`, are not dependencies and are left out.
+fn remapping_targets(root: &Path) -> Vec<PathBuf> {
+    let mut remappings: Vec<String> = fs::read_to_string(root.join("remappings.txt"))
+        .map(|text| text.lines().map(String::from).collect())
+        .unwrap_or_default();
+
+    let foundry = fs::read_to_string(root.join("foundry.toml"))
+        .ok()
+        .and_then(|text| text.parse::<toml::Table>().ok());
+    // Foundry's default layout, plus the directories the profiles configure
+    let mut project_directories: Vec<PathBuf> = ["src", "test", "script"]
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+    if let Some(profiles) = foundry.as_ref().and_then(|t| t.get("profile")?.as_table()) {
+        remappings.extend(
+            profiles
+                .values()
+                .filter_map(|profile| profile.get("remappings")?.as_array())
+                .flatten()
+                .filter_map(|remapping| remapping.as_str().map(String::from)),
+        );
+        project_directories.extend(
+            profiles
+                .values()
+                .flat_map(|profile| ["src", "test", "script"].map(|key| profile.get(key)))
+                .filter_map(|directory| directory?.as_str())
+                .map(|directory| PathBuf::from(directory.strip_prefix("./").unwrap_or(directory))),
+        );
+    }
+
+    // Each remapping reads `[context:]prefix=target`
+    remappings
+        .iter()
+        .filter_map(|remapping| remapping.split_once('=')?.1.trim().into())
+        .map(|target: &str| target.strip_prefix("./").unwrap_or(target))
+        .filter(|target| !target.is_empty() && !Path::new(target).is_absolute())
+        .map(PathBuf::from)
+        .filter(|target| {
+            target.starts_with("lib")
+                || target.iter().any(|dir| dir == "node_modules")
+                || !project_directories
+                    .iter()
+                    .any(|directory| target.starts_with(directory))
+        })
+        .collect()
+}
+
+/// Returns true for a directory, relative to the analyzed one, that holds dependencies or tests
+fn is_dependency_directory(relative: &Path) -> bool {
+    let at_root = relative.components().count() == 1;
+    relative.file_name().is_some_and(|name| {
+        name == "node_modules" || at_root && DEPENDENCY_DIRECTORIES.iter().any(|dir| name == *dir)
+    })
+}
+
+/// Compiles globs into a set
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet, ConstructorAnalyzerError> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern)?);
+    }
+    Ok(builder.build()?)
+}
+
+/// Finds the top of the git repository that contains `path`
+fn repository_root(path: &Path) -> Option<PathBuf> {
+    fs::canonicalize(path)
+        .ok()?
+        .ancestors()
+        .find(|directory| directory.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+/// Matches a path relative to the analyzed directory `root` against globs that are either
+/// relative to that directory or absolute
+fn matches_glob(set: &GlobSet, root: &Path, relative: &Path) -> bool {
+    set.is_match(relative) || set.is_match(root.join(relative))
+}
+
+/// Cuts the source of a definition out of a file, with `context_lines` lines around it
+fn source_excerpt(
+    contents: &str,
+    span: Range<usize>,
+    context_lines: usize,
+    unvalidated_parameters: Vec<ParameterSpan>,
+) -> SourceExcerpt {
+    let line_start = contents[..span.start]
+        .rfind('\n')
+        .map_or(0, |newline| newline + 1);
+    let before: Vec<&str> = contents[..line_start].lines().collect();
+    let after = contents[span.end..]
+        .split_once('\n')
+        .map_or("", |(_, rest)| rest);
+
+    SourceExcerpt {
+        code: contents[span.clone()].to_string(),
+        start_line: line_column(contents, span.start).0,
+        context_before: before[before.len().saturating_sub(context_lines)..]
+            .iter()
+            .map(|line| line.to_string())
+            .collect(),
+        context_after: after
+            .lines()
+            .take(context_lines)
+            .map(String::from)
+            .collect(),
+        unvalidated_parameters,
+    }
+}
+
+/// Returns the text of a capture group from the original source
+///
+/// Captures are taken on the masked source, which shares byte offsets with the original.
+fn capture_text<'a>(contents: &'a str, captures: &Captures, group: usize) -> &'a str {
+    captures
+        .get(group)
+        .map_or("", |m| contents[m.start()..m.end()].trim())
+}
+
+/// Returns the byte range of the trimmed body of a function whose header match ends at
+/// its opening brace
+fn body_span(contents: &str, masked: &str, captures: &Captures) -> Option<Range<usize>> {
+    let open = captures.get(0)?.end() - 1;
+    let close = find_matching_brace(masked, open)?;
+    let inner = &contents[open + 1..close];
+    let start = open + 1 + (inner.len() - inner.trim_start().len());
+    let end = (close - (inner.len() - inner.trim_end().len())).max(start);
+    Some(start..end)
+}
+
+/// Converts a position relative to a snippet into one relative to the file, given the
+/// file position where the snippet starts
+fn offset_position((line, column): (usize, usize), origin: (usize, usize)) -> (usize, usize) {
+    if line == 1 {
+        (origin.0, origin.1 + column - 1)
+    } else {
+        (origin.0 + line - 1, column)
+    }
+}
+
+/// Returns the 1-based line and column (in characters) of a byte offset
+fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+/// Finds the innermost container enclosing a match
+fn enclosing_container<'a>(containers: &'a [Container], m: Option<Match>) -> Option<&'a Container> {
+    let position = m?.start();
+    containers
+        .iter()
+        .filter(|container| container.start < position && position < container.end)
+        .min_by_key(|container| container.end - container.start)
+}
+
+/// Finds the closing brace matching the opening brace at `open`
+fn find_matching_brace(masked: &str, open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (offset, byte) in masked.as_bytes()[open..].iter().enumerate() {
+        match byte {
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + offset);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Patterns for [`classify_usage`], compiled once
+///
+/// Each pattern captures the identifier in the position of interest, which is then
+/// compared with the parameter name, so no pattern depends on the parameter.
+struct UsagePatterns {
+    identifier: Regex,
+    emit: Regex,
+    delegatecall: Regex,
+    interface_call: Regex,
+    low_level_call: Regex,
+    member_transfer: Regex,
+    payable_transfer: Regex,
+    transfer_to: Regex,
+    transfer_from_to: Regex,
+    assignment: Regex,
+    push: Regex,
+    call: Regex,
+    declaration: Regex,
+}
+
+static USAGE_PATTERNS: LazyLock<UsagePatterns> = LazyLock::new(|| {
+    let regex = |pattern: &str| Regex::new(pattern).expect("usage regex is valid");
+    UsagePatterns {
+        identifier: regex(r"\b\w+\b"),
+        emit: regex(r"\bemit\s+[^;]*;"),
+        delegatecall: regex(r"\b(\w+)\s*\.\s*delegatecall\b"),
+        // Interface casts such as `IERC20(p).transfer(...)`
+        interface_call: regex(r"\b(\w+)\s*\(\s*(\w+)\s*\)\s*\.\s*\w+"),
+        low_level_call: regex(r"\b(\w+)\s*\.\s*(?:call|staticcall)\b"),
+        member_transfer: regex(r"\b(\w+)\s*\.\s*(?:transfer|send)\s*\("),
+        payable_transfer: regex(r"payable\s*\(\s*(\w+)\s*\)\s*\.\s*(?:transfer|send|call)\b"),
+        transfer_to: regex(
+            r"\b(?:safeTransfer|transfer|_transfer|mint|_mint|safeMint|_safeMint)\s*\(\s*(\w+)\s*[,)]",
+        ),
+        transfer_from_to: regex(
+            r"\b(?:safeTransferFrom|transferFrom|_transfer)\s*\([^,;]*,\s*(\w+)\s*[,)]",
+        ),
+        // The statement must end after the value; the `;` is checked separately so that it
+        // can start the next match
+        assignment: regex(r"(?m)(?:^|[;{})])\s*(\w+)(?:\s*(?:\[[^\]]*\]|\.\s*\w+))*\s*=\s*(\w+)"),
+        push: regex(r"\b(\w+)\s*\.\s*push\s*\(\s*(\w+)\s*\)"),
+        call: regex(r"\b(\w+)\s*\(([^;]*)"),
+        declaration: regex(
+            r"\b(?:address(?:\s+payable)?|bool|string|bytes\d*|u?int\d*|[A-Z]\w*)(?:\s*\[\d*\])*\s+(?:memory\s+|storage\s+|calldata\s+)?(\w+)\s*[=;]",
+        ),
+    }
+});
+
+/// Classifies how `parameter` is used in a masked function body
+///
+/// `parameters` lists every address parameter and `immutables` the immutable state
+/// variables of the enclosing contract.
+fn classify_usage(
+    body: &str,
+    parameter: &str,
+    parameters: &[&str],
+    immutables: &[String],
+) -> Vec<ParameterUsage> {
+    let patterns = &*USAGE_PATTERNS;
+    let mentions = |text: &str| {
+        patterns
+            .identifier
+            .find_iter(text)
+            .any(|m| m.as_str() == parameter)
+    };
+
+    if !mentions(body) {
+        return vec![ParameterUsage::Unused];
+    }
+
+    // Event arguments are not uses that can go wrong with a zero address
+    let without_emits = patterns.emit.replace_all(body, "");
+    if !mentions(&without_emits) {
+        return vec![ParameterUsage::EmittedOnly];
+    }
+    let body = &*without_emits;
+    // Whether `regex` matches with `parameter` in capture group `group`
+    let found = |regex: &Regex, group: usize| {
+        regex
+            .captures_iter(body)
+            .any(|captures| captures.get(group).is_some_and(|m| m.as_str() == parameter))
+    };
+
+    let mut usage = Vec::new();
+
+    if found(&patterns.delegatecall, 1) {
+        usage.push(ParameterUsage::DelegatecallTarget);
+    }
+
+    // Casts such as `payable(p)` or `address(p)` are not interface calls
+    let interface_call = patterns.interface_call.captures_iter(body).any(|captures| {
+        &captures[2] == parameter
+            && !matches!(&captures[1], "payable" | "address" | "require" | "if")
+    });
+    if interface_call || found(&patterns.low_level_call, 1) {
+        usage.push(ParameterUsage::CallTarget);
+    }
+
+    if found(&patterns.member_transfer, 1)
+        || found(&patterns.payable_transfer, 1)
+        || found(&patterns.transfer_to, 1)
+        || found(&patterns.transfer_from_to, 1)
+    {
+        usage.push(ParameterUsage::TransferRecipient);
+    }
+
+    let locals = local_variables(body);
+    let assignments = patterns.assignment.captures_iter(body).filter(|captures| {
+        let end = captures.get(0).map_or(0, |m| m.end());
+        &captures[2] == parameter && body[end..].trim_start().starts_with(';')
+    });
+    let pushes = patterns
+        .push
+        .captures_iter(body)
+        .filter(|captures| &captures[2] == parameter);
+    let assigned: Vec<&str> = assignments
+        .chain(pushes)
+        .filter_map(|captures| captures.get(1).map(|m| m.as_str()))
+        .filter(|name| !locals.contains(name) && !parameters.contains(name))
+        .collect();
+    if assigned
+        .iter()
+        .any(|name| immutables.iter().any(|immutable| immutable == name))
+    {
+        usage.push(ParameterUsage::StoredToImmutable);
+    }
+    if assigned
+        .iter()
+        .any(|name| !immutables.iter().any(|immutable| immutable == name))
+    {
+        usage.push(ParameterUsage::StoredToState);
+    }
+
+    // Any other call taking the parameter, once the specific uses above are ruled out
+    let passed = patterns.call.captures_iter(body).any(|captures| {
+        mentions(&captures[2])
+            && !matches!(
+                &captures[1],
+                "require" | "assert" | "if" | "while" | "for" | "revert" | "payable" | "address"
+            )
+    });
+    if passed
+        && !usage.iter().any(|u| {
+            matches!(
+                u,
+                ParameterUsage::CallTarget | ParameterUsage::TransferRecipient
+            )
+        })
+    {
+        usage.push(ParameterUsage::PassedToFunction);
+    }
+
+    if usage.is_empty() {
+        usage.push(ParameterUsage::Other);
+    }
+    usage
+}
+
+/// Lists the names of variables declared in a function body
+fn local_variables(body: &str) -> Vec<&str> {
+    USAGE_PATTERNS
+        .declaration
+        .captures_iter(body)
+        .filter_map(|captures| captures.get(1).map(|m| m.as_str()))
+        .collect()
+}
+
+/// Parses the text between a parameter list and the opening brace
+///
+/// Keywords set visibility and mutability; `returns (...)`, `virtual` and `override`
+/// are skipped, and any other identifier is kept as a modifier invocation with its arguments.
+fn parse_header_attributes(attributes: &str) -> HeaderAttributes {
+    let mut header = HeaderAttributes::default();
+    let mut rest = attributes.trim_start();
+
+    while !rest.is_empty() {
+        let word_len = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$' || c == '.'))
+            .unwrap_or(rest.len());
+        if word_len == 0 {
+            // Stray punctuation; skip one character
+            let skip = rest.chars().next().map_or(1, char::len_utf8);
+            rest = rest[skip..].trim_start();
+            continue;
+        }
+
+        let word = &rest[..word_len];
+        rest = rest[word_len..].trim_start();
+
+        // Take a balanced parenthesized argument list if one follows
+        let mut invocation = word.to_string();
+        if rest.starts_with('(') {
+            let mut depth = 0usize;
+            let mut end = rest.len();
+            for (offset, c) in rest.char_indices() {
+                match c {
+                    '(' => depth += 1,
+                    ')' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            end = offset + 1;
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            invocation.push_str(&rest[..end]);
+            rest = rest[end..].trim_start();
+        }
+
+        match word {
+            "external" => header.visibility = Some(Visibility::External),
+            "public" => header.visibility = Some(Visibility::Public),
+            "internal" => header.visibility = Some(Visibility::Internal),
+            "private" => header.visibility = Some(Visibility::Private),
+            "pure" => header.mutability = StateMutability::Pure,
+            "view" => header.mutability = StateMutability::View,
+            "payable" => header.mutability = StateMutability::Payable,
+            "returns" | "virtual" | "override" => {}
+            _ => header.modifiers.push(invocation),
+        }
+    }
+
+    header
+}
+
+/// Replaces comments and string literal contents with spaces, keeping byte offsets and newlines
+pub(crate) fn mask_comments_and_strings(source: &str) -> String {
+    let bytes = source.as_bytes();
+    let mut masked = bytes.to_vec();
+    let mut i = 0;
+
+    let blank = |masked: &mut Vec<u8>, from: usize, to: usize| {
+        for byte in &mut masked[from..to] {
+            if *byte != b'\n' {
+                *byte = b' ';
+            }
+        }
+    };
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                let end = bytes[i..]
+                    .iter()
+                    .position(|&b| b == b'\n')
+                    .map_or(bytes.len(), |p| i + p);
+                blank(&mut masked, i, end);
+                i = end;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let end = source[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |p| i + 2 + p + 2);
+                blank(&mut masked, i, end);
+                i = end;
+            }
+            quote @ (b'"' | b'\'') => {
+                let mut end = i + 1;
+                while end < bytes.len() && bytes[end] != quote && bytes[end] != b'\n' {
+                    if bytes[end] == b'\\' {
+                        end += 1;
+                    }
+                    end += 1;
+                }
+                let end = end.min(bytes.len());
+                blank(&mut masked, i + 1, end);
+                i = end + 1;
+            }
+            _ => i += 1,
+        }
+    }
+
+    // Only ASCII bytes were replaced by ASCII spaces, and every multi-byte
+    // character was blanked entirely or left untouched
+    String::from_utf8(masked).unwrap_or_else(|_| source.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::Summary;
 
     #[test]
     fn test_extract_address_arguments() {
@@ -453,4 +1749,732 @@ mod tests {
                 .contains(&ValidationType::RequireStatement)
         );
     }
+
+    #[test]
+    fn test_analyze_source_classifies_function_kinds() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+
+        let source = r#"
+            function freeHelper(address who) pure returns (address) { return who; }
+
+            interface IVault {
+                function deposit(address token) external;
+            }
+
+            library SafeAddr {
+                function check(address a) internal pure {
+                    if (a == address(0)) { revert("zero }"); }
+                }
+            }
+
+            contract Vault {
+                modifier onlyAt(address who) { require(msg.sender == who); _; }
+
+                function deposit(address token) external onlyAt(token) {}
+            }
+        "#;
+        let results = analyzer.analyze_source(source, "Vault.sol", true);
+
+        let kinds: Vec<(FunctionType, Option<String>)> = results
+            .into_iter()
+            .map(|r| (r.function_type, r.contract_name))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (FunctionType::FreeFunction("freeHelper".to_string()), None),
+                (
+                    FunctionType::LibraryFunction("check".to_string()),
+                    Some("SafeAddr".to_string())
+                ),
+                (
+                    FunctionType::Regular("deposit".to_string()),
+                    Some("Vault".to_string())
+                ),
+                (
+                    FunctionType::Modifier("onlyAt".to_string()),
+                    Some("Vault".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_analyze_source_finds_fallback_and_receive() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+
+        let source = r#"
+            interface IHook { function receive(address from) external; }
+
+            contract Proxy {
+                address implementation;
+
+                receive() external payable {}
+
+                // fallback() must not be matched in comments
+                fallback(bytes calldata data) external payable returns (bytes memory) {
+                    if (hook.receive(msg.sender)) { }
+                    (, bytes memory out) = implementation.delegatecall(data);
+                    return out;
+                }
+            }
+        "#;
+        let masked = mask_comments_and_strings(source);
+        let containers = analyzer.find_containers(&masked);
+        let definitions = analyzer.find_definitions(source, &masked, &containers, true);
+
+        let units: Vec<(FunctionType, &str)> = definitions
+            .iter()
+            .map(|d| (d.function_type.clone(), d.arguments))
+            .collect();
+        assert_eq!(
+            units,
+            vec![
+                (FunctionType::Receive, ""),
+                (FunctionType::Fallback, "bytes calldata data"),
+            ]
+        );
+        assert_eq!(
+            parse_header_attributes(definitions[1].attributes).mutability,
+            StateMutability::Payable
+        );
+
+        // Like other named kinds, they are only reported with address parameters
+        assert!(
+            analyzer
+                .analyze_source(source, "Proxy.sol", true)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_analyze_source_skips_other_kinds_without_all_functions() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+
+        let source = "contract A { modifier m(address a) { _; } constructor(address b) {} }";
+        let results = analyzer.analyze_source(source, "A.sol", false);
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].function_type, FunctionType::Constructor);
+    }
+
+    #[test]
+    fn test_mask_comments_and_strings_keeps_offsets() {
+        let source = "a { // }\n/* { */ \"}\" b";
+        let masked = mask_comments_and_strings(source);
+
+        assert_eq!(masked.len(), source.len());
+        assert_eq!(masked, "a {     \n        \" \" b");
+    }
+
+    #[test]
+    fn test_parse_header_attributes() {
+        let header = parse_header_attributes(
+            "external payable virtual override(A, B) onlyRole(keccak256(\"ADMIN\")) nonReentrant returns (address)",
+        );
+
+        assert_eq!(
+            header,
+            HeaderAttributes {
+                visibility: Some(Visibility::External),
+                mutability: StateMutability::Payable,
+                modifiers: vec![
+                    "onlyRole(keccak256(\"ADMIN\"))".to_string(),
+                    "nonReentrant".to_string()
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_analyze_source_records_header_attributes() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+
+        let source = r#"
+            function helper(address a) pure {}
+
+            contract Vault {
+                function setTreasury(address t) public onlyOwner { treasury = t; }
+            }
+        "#;
+        let results = analyzer.analyze_source(source, "Vault.sol", true);
+
+        assert_eq!(results[0].visibility, Some(Visibility::Internal));
+        assert_eq!(results[0].mutability, StateMutability::Pure);
+        assert_eq!(results[1].visibility, Some(Visibility::Public));
+        assert_eq!(results[1].mutability, StateMutability::NonPayable);
+        assert_eq!(results[1].modifiers, vec!["onlyOwner"]);
+    }
+
+    #[test]
+    fn test_access_controlled_functions_have_trusted_caller() {
+        let mut options = AnalyzerOptions::default();
+        options
+            .access_control_modifiers
+            .push("onlyKeeper".to_string());
+        let analyzer = ConstructorAnalyzer::with_options(options).unwrap();
+
+        let source = r#"
+            contract Vault {
+                function setTreasury(address t) external onlyRole(ADMIN) { treasury = t; }
+                function setKeeper(address k) external onlyKeeper { keeper = k; }
+                function setGuardian(address g) external {
+                    require(msg.sender == owner, "not owner");
+                    guardian = g;
+                }
+                function setRouter(address r) external {
+                    if (!hasRole(KEEPER, msg.sender)) revert();
+                    router = r;
+                }
+                function setHook(address h) external {
+                    if (_owner() != msg.sender) revert NotOwner();
+                    hook = h;
+                }
+                function deposit(address from) external { emit Deposit(from); }
+                function transfer(address _to) external { require(_to != msg.sender); }
+                function sweep(address _to) external {
+                    // TODO: require(owner == msg.sender)
+                    payable(_to).transfer(1);
+                }
+            }
+        "#;
+        let results = analyzer.analyze_source(source, "Vault.sol", true);
+
+        let controls: Vec<(bool, Vec<String>)> = results
+            .into_iter()
+            .map(|r| (r.trusted_caller, r.access_controls))
+            .collect();
+        assert_eq!(
+            controls,
+            vec![
+                (true, vec!["onlyRole(ADMIN)".to_string()]),
+                (true, vec!["onlyKeeper".to_string()]),
+                (true, vec!["msg.sender == owner".to_string()]),
+                (true, vec!["hasRole(KEEPER, msg.sender)".to_string()]),
+                (true, vec!["_owner() != msg.sender".to_string()]),
+                (false, vec![]),
+                (false, vec![]),
+                (false, vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_findings_get_severity_from_parameter_context() {
+        let mut options = AnalyzerOptions::default();
+        options
+            .severity_overrides
+            .insert(SeverityRule::TokenRecipient, Severity::High);
+        let analyzer = ConstructorAnalyzer::with_options(options).unwrap();
+
+        let source = r#"
+            contract Vault {
+                address public treasury;
+
+                constructor(address _treasury, address _hook) {
+                    treasury = _treasury;
+                    emit Hooked(_hook);
+                }
+
+                function execute(address target, bytes calldata data) external {
+                    target.delegatecall(data);
+                }
+
+                function pay(address token, address payable to) external {
+                    IERC20(token).transfer(to, 1);
+                }
+
+                function setTreasury(address t) external onlyOwner {
+                    address previous = treasury;
+                    treasury = t;
+                }
+            }
+        "#;
+        let results = analyzer.analyze_source(source, "Vault.sol", true);
+
+        let findings: Vec<(String, Severity, SeverityRule)> = results
+            .iter()
+            .flat_map(|r| &r.findings)
+            .map(|f| (f.parameter.clone(), f.severity, f.rule))
+            .collect();
+        assert_eq!(
+            findings,
+            vec![
+                (
+                    "_treasury".to_string(),
+                    Severity::High,
+                    SeverityRule::InitializerState
+                ),
+                (
+                    "_hook".to_string(),
+                    Severity::Low,
+                    SeverityRule::Unclassified
+                ),
+                (
+                    "target".to_string(),
+                    Severity::Critical,
+                    SeverityRule::DelegatecallTarget
+                ),
+                (
+                    "token".to_string(),
+                    Severity::High,
+                    SeverityRule::CallTarget
+                ),
+                (
+                    "to".to_string(),
+                    Severity::High,
+                    SeverityRule::TokenRecipient
+                ),
+                (
+                    "t".to_string(),
+                    Severity::Low,
+                    SeverityRule::AccessControlledSetter
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_findings_classify_parameter_usage() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+
+        let source = r#"
+            contract Vault {
+                address public immutable token;
+                address public treasury;
+
+                constructor(
+                    address _token,
+                    address _treasury,
+                    address _hook,
+                    address _unused,
+                    address _admin,
+                    address _other
+                ) {
+                    token = _token;
+                    treasury = _treasury;
+                    emit Hooked(_hook);
+                    // _unused is only mentioned in this comment
+                    _grantRole(ADMIN_ROLE, _admin);
+                    bool same = _other == msg.sender;
+                }
+            }
+        "#;
+        let results = analyzer.analyze_source(source, "Vault.sol", false);
+
+        let usage: Vec<(String, Vec<ParameterUsage>)> = results[0]
+            .findings
+            .iter()
+            .map(|f| (f.parameter.clone(), f.usage.clone()))
+            .collect();
+        assert_eq!(
+            usage,
+            vec![
+                (
+                    "_token".to_string(),
+                    vec![ParameterUsage::StoredToImmutable]
+                ),
+                ("_treasury".to_string(), vec![ParameterUsage::StoredToState]),
+                ("_hook".to_string(), vec![ParameterUsage::EmittedOnly]),
+                ("_unused".to_string(), vec![ParameterUsage::Unused]),
+                ("_admin".to_string(), vec![ParameterUsage::PassedToFunction]),
+                ("_other".to_string(), vec![ParameterUsage::Other]),
+            ]
+        );
+        assert_eq!(results[0].findings[0].rule, SeverityRule::InitializerState);
+    }
+
+    #[test]
+    fn test_validation_evidence_records_kind_source_and_location() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+
+        let source = "contract A {\n    constructor(address _a, address _b) {\n        require(_a != address(0), \"zero\");\n        if (_b == address(0)) revert();\n    }\n}\n";
+        let results = analyzer.analyze_source(source, "A.sol", false);
+
+        assert_eq!(
+            results[0].validation_evidence,
+            vec![
+                ValidationEvidence {
+                    variable: "_a".to_string(),
+                    kind: ValidationType::RequireStatement,
+                    source: "require(_a != address(0), \"zero\")".to_string(),
+                    line: 3,
+                    column: 9,
+                },
+                ValidationEvidence {
+                    variable: "_b".to_string(),
+                    kind: ValidationType::EqualityCheck,
+                    source: "_b == address(0)".to_string(),
+                    line: 4,
+                    column: 13,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_source_excerpt_keeps_original_text_and_context() {
+        let analyzer = ConstructorAnalyzer::with_options(AnalyzerOptions {
+            source_context: Some(1),
+            ..AnalyzerOptions::default()
+        })
+        .unwrap();
+
+        let source = "contract A {\n    address owner;\n    constructor(address _owner) {\n        owner = _owner; // set\n    }\n    uint256 x;\n}\n";
+        let results = analyzer.analyze_source(source, "A.sol", false);
+        let excerpt = results[0].source.as_ref().unwrap();
+
+        assert_eq!(
+            excerpt.code,
+            "constructor(address _owner) {\n        owner = _owner; // set\n    }"
+        );
+        assert_eq!(excerpt.start_line, 3);
+        assert_eq!(excerpt.context_before, vec!["    address owner;"]);
+        assert_eq!(excerpt.context_after, vec!["    uint256 x;"]);
+        let span = &excerpt.unvalidated_parameters[0];
+        assert_eq!(&excerpt.code[span.start..span.end], "_owner");
+        assert_eq!(span.start, 20);
+
+        // Without the option no source is kept
+        let results = ConstructorAnalyzer::new()
+            .unwrap()
+            .analyze_source(source, "A.sol", false);
+        assert!(results[0].source.is_none());
+    }
+
+    #[test]
+    fn test_validator_calls_validate_arguments() {
+        let analyzer = ConstructorAnalyzer::with_options(AnalyzerOptions {
+            validators: vec!["Checks.nonZero".to_string()],
+            ..AnalyzerOptions::default()
+        })
+        .unwrap();
+
+        let source = "contract A {\n    constructor(address _a, address _b, address _c) {\n        Checks.nonZero(_a, _b);\n        other(_c);\n    }\n}\n";
+        let results = analyzer.analyze_source(source, "A.sol", false);
+
+        assert_eq!(results[0].validated_variables, vec!["_a", "_b"]);
+        assert_eq!(results[0].missing_validations, vec!["_c"]);
+        assert_eq!(
+            results[0].validation_types,
+            vec![ValidationType::ValidatorCall]
+        );
+        assert_eq!(
+            results[0].validation_evidence[1].source,
+            "Checks.nonZero(_a, _b)"
+        );
+        assert_eq!(results[0].validation_evidence[1].line, 3);
+    }
+
+    #[test]
+    fn test_suppression_comments_move_findings_aside() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let source = r#"
+contract Vault {
+    address a;
+    address b;
+    address c;
+
+    // construstor-disable-next-line zero-address:_b -- zero disables the hook
+    constructor(address _a, address _b) {
+        a = _a;
+        b = _b;
+    }
+
+    function initialize(
+        address _c // construstor-disable-line
+    ) external {
+        c = _c;
+    }
+}
+"#;
+
+        let results = analyzer.analyze_source(source, "Vault.sol", false);
+        let parameters = |findings: &[Finding]| -> Vec<String> {
+            findings.iter().map(|f| f.parameter.clone()).collect()
+        };
+
+        assert_eq!(parameters(&results[0].findings), vec!["_a"]);
+        assert_eq!(parameters(&results[0].suppressed_findings), vec!["_b"]);
+        assert!(results[1].findings.is_empty());
+        assert_eq!(parameters(&results[1].suppressed_findings), vec!["_c"]);
+        assert_eq!(results[0].missing_validations, vec!["_a"]);
+        assert!(results[1].missing_validations.is_empty());
+
+        let summary = Summary::from_results(&results);
+        assert_eq!(summary.suppressed_findings, 2);
+        assert_eq!(summary.overall.fully_validated, 1);
+        assert_eq!(summary.overall.not_validated, 1);
+    }
+
+    #[test]
+    fn test_natspec_marks_parameters_allowed_to_be_zero() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let source = r#"
+contract Referral {
+    address owner;
+    address referrer;
+
+    /// @param _owner The owner
+    /// @param _referrer Optional referrer, may be address(0)
+    constructor(address _owner, address _referrer) {
+        owner = _owner;
+        referrer = _referrer;
+    }
+}
+"#;
+
+        let results = analyzer.analyze_source(source, "Referral.sol", false);
+
+        assert_eq!(results[0].zero_address_allowed, vec!["_referrer"]);
+        assert_eq!(results[0].missing_validations, vec!["_owner"]);
+        assert_eq!(results[0].findings.len(), 1);
+        assert_eq!(results[0].findings[0].parameter, "_owner");
+    }
+
+    #[test]
+    fn test_analyze_path_skips_ignored_and_dependency_files() {
+        let root = tempfile::tempdir().unwrap();
+        let source = "contract A {\n    constructor(address _a) {}\n}\n";
+        for file in [
+            "src/Vault.sol",
+            "src/mocks/Mock.sol",
+            "src/generated/Gen.sol",
+            "src/Local.sol",
+            "lib/forge-std/Test.sol",
+            "node_modules/@oz/Ownable.sol",
+            "test/Vault.t.sol",
+        ] {
+            let path = root.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, source).unwrap();
+        }
+        fs::write(root.path().join(".gitignore"), "generated/\n").unwrap();
+        fs::write(root.path().join(IGNORE_FILE_NAME), "Local.sol\n").unwrap();
+
+        let analyzed = |options: AnalyzerOptions| -> Vec<String> {
+            ConstructorAnalyzer::with_options(options)
+                .unwrap()
+                .analyze_path(root.path().to_str().unwrap(), false)
+                .unwrap()
+                .iter()
+                .map(|result| {
+                    let path = Path::new(&result.file_path);
+                    let relative = path.strip_prefix(root.path()).unwrap();
+                    relative.to_string_lossy().replace('\\', "/")
+                })
+                .collect()
+        };
+
+        let exclude_mocks = AnalyzerOptions {
+            exclude: vec!["src/mocks".to_string()],
+            ..AnalyzerOptions::default()
+        };
+        assert_eq!(analyzed(exclude_mocks), vec!["src/Vault.sol"]);
+
+        let everything = AnalyzerOptions {
+            use_ignore_files: false,
+            skip_dependencies: false,
+            ..AnalyzerOptions::default()
+        };
+        assert_eq!(analyzed(everything).len(), 7);
+    }
+
+    #[test]
+    fn test_analyze_path_classifies_file_origin() {
+        let root = tempfile::tempdir().unwrap();
+        let source = "contract A {\n    constructor(address _a) {}\n}\n";
+        for file in [
+            "src/Vault.sol",
+            "dependencies/oz/Ownable.sol",
+            "lib/forge-std/Base.sol",
+            "script/Deploy.s.sol",
+            "src/Vault.t.sol",
+        ] {
+            let path = root.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, source).unwrap();
+        }
+        fs::write(
+            root.path().join("foundry.toml"),
+            "[profile.default]\nremappings = [\"@oz/=dependencies/oz/\"]\n",
+        )
+        .unwrap();
+
+        let analyzer = ConstructorAnalyzer::with_options(AnalyzerOptions {
+            skip_dependencies: false,
+            ..AnalyzerOptions::default()
+        })
+        .unwrap();
+        let origins: Vec<(String, FileOrigin)> = analyzer
+            .analyze_path(root.path().to_str().unwrap(), false)
+            .unwrap()
+            .into_iter()
+            .map(|result| (result.file_name, result.origin))
+            .collect();
+
+        assert_eq!(
+            origins,
+            vec![
+                ("Ownable.sol".to_string(), FileOrigin::Dependency),
+                ("Base.sol".to_string(), FileOrigin::Dependency),
+                ("Deploy.s.sol".to_string(), FileOrigin::TestScript),
+                ("Vault.sol".to_string(), FileOrigin::FirstParty),
+                ("Vault.t.sol".to_string(), FileOrigin::TestScript),
+            ]
+        );
+
+        let single_file = root.path().join("src/Vault.t.sol");
+        let results = analyzer
+            .analyze_path(single_file.to_str().unwrap(), false)
+            .unwrap();
+        assert_eq!(results[0].origin, FileOrigin::TestScript);
+    }
+
+    #[test]
+    fn test_fingerprints_do_not_depend_on_how_the_path_is_given() {
+        // A path relative to the working directory, as when running `construstor .`
+        let root = tempfile::tempdir_in(env!("CARGO_MANIFEST_DIR")).unwrap();
+        let relative = root
+            .path()
+            .strip_prefix(std::env::current_dir().unwrap())
+            .unwrap_or(root.path())
+            .to_path_buf();
+        let absolute = fs::canonicalize(root.path()).unwrap();
+        fs::create_dir_all(absolute.join("src")).unwrap();
+        fs::write(
+            absolute.join("src/Vault.sol"),
+            "contract Vault {\n    address a;\n    constructor(address _a) { a = _a; }\n}\n",
+        )
+        .unwrap();
+
+        let fingerprints = |analyzer: &ConstructorAnalyzer, path: &Path| -> Vec<String> {
+            analyzer
+                .analyze_path(path.to_str().unwrap(), false)
+                .unwrap()
+                .into_iter()
+                .flat_map(|result| result.findings)
+                .map(|finding| finding.fingerprint)
+                .collect()
+        };
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let expected = fingerprints(&analyzer, &absolute);
+        assert_eq!(expected.len(), 1);
+        assert_eq!(fingerprints(&analyzer, &relative), expected);
+        assert_eq!(fingerprints(&analyzer, &relative.join("src")), expected);
+
+        // Relative to a fixed root, analyzing a subdirectory or a file gives the same result
+        let analyzer = ConstructorAnalyzer::with_options(AnalyzerOptions {
+            fingerprint_root: Some(relative.clone()),
+            ..AnalyzerOptions::default()
+        })
+        .unwrap();
+        let expected = fingerprints(&analyzer, &absolute);
+        assert_eq!(fingerprints(&analyzer, &relative), expected);
+        assert_eq!(fingerprints(&analyzer, &relative.join("src")), expected);
+        assert_eq!(
+            fingerprints(&analyzer, &absolute.join("src/Vault.sol")),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_fingerprints_are_relative_to_the_repository_root() {
+        let root = tempfile::tempdir().unwrap();
+        let root = fs::canonicalize(root.path()).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(
+            root.join("src/Vault.sol"),
+            "contract Vault {\n    address a;\n    constructor(address _a) { a = _a; }\n}\n",
+        )
+        .unwrap();
+
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let expected = vec![fingerprint::finding_fingerprint(
+            "src/Vault.sol",
+            Some("Vault"),
+            "constructor(address)",
+            "_a",
+        )];
+        for path in [root.clone(), root.join("src"), root.join("src/Vault.sol")] {
+            let fingerprints: Vec<String> = analyzer
+                .analyze_path(path.to_str().unwrap(), false)
+                .unwrap()
+                .into_iter()
+                .flat_map(|result| result.findings)
+                .map(|finding| finding.fingerprint)
+                .collect();
+            assert_eq!(fingerprints, expected, "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn test_fingerprints_survive_body_and_header_edits() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let finding = |source: &str| {
+            let results = analyzer.analyze_source(source, "Vault.sol", true);
+            let finding = results[0].findings[0].clone();
+            (finding.rule, finding.fingerprint)
+        };
+
+        let (rule, fingerprint) = finding(
+            "contract Vault { function setPool(address _pool) external { pool = _pool; } }",
+        );
+        let edits = [
+            // A new statement that changes the rule
+            "contract Vault { function setPool(address _pool) external { pool = _pool; _pool.call(\"\"); } }",
+            // A modifier that makes the function trusted-caller only
+            "contract Vault { function setPool(address _pool) external onlyOwner { pool = _pool; } }",
+            // A reformatted header with a different visibility
+            "contract Vault {\n    function setPool(\n        address _pool\n    ) public virtual {\n        pool = _pool;\n    }\n}",
+        ];
+        for edit in edits {
+            assert_eq!(finding(edit).1, fingerprint, "{edit}");
+        }
+        assert_ne!(finding(edits[0]).0, rule);
+        assert_ne!(finding(edits[1]).0, rule);
+    }
+
+    #[test]
+    fn test_self_alias_remappings_keep_project_files_first_party() {
+        let root = tempfile::tempdir().unwrap();
+        let source = "contract A {\n    constructor(address _a) {}\n}\n";
+        for file in [
+            "src/Vault.sol",
+            "contracts/Pool.sol",
+            "vendor/oz/Ownable.sol",
+        ] {
+            let path = root.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, source).unwrap();
+        }
+        fs::write(
+            root.path().join("remappings.txt"),
+            "@app/=src/\n@pool/=./contracts/\n@oz/=vendor/oz/\n",
+        )
+        .unwrap();
+        fs::write(
+            root.path().join("foundry.toml"),
+            "[profile.default]\nsrc = \"contracts\"\n",
+        )
+        .unwrap();
+
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let origins: Vec<(String, FileOrigin)> = analyzer
+            .analyze_path(root.path().to_str().unwrap(), false)
+            .unwrap()
+            .into_iter()
+            .map(|result| (result.file_name, result.origin))
+            .collect();
+
+        assert_eq!(
+            origins,
+            vec![
+                ("Pool.sol".to_string(), FileOrigin::FirstParty),
+                ("Vault.sol".to_string(), FileOrigin::FirstParty),
+                ("Ownable.sol".to_string(), FileOrigin::Dependency),
+            ]
+        );
+    }
 }
@@ -0,0 +1,240 @@
+//! Baselines of accepted findings, for adopting the tool on existing code
+//!
+//! A baseline records the fingerprint of every finding of a run. Later runs drop the
+//! findings it records, so that only new ones are reported and counted by the failure
+//! policy, and list the recorded findings that no longer occur.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::output::report_path;
+use crate::severity::SeverityRule;
+use crate::types::AnalysisResult;
+
+/// Baseline file written by `construstor baseline create` when no path is given
+pub const BASELINE_FILE_NAME: &str = "construstor-baseline.json";
+
+/// Version of the baseline file layout
+pub const BASELINE_VERSION: u32 = 1;
+
+/// A finding recorded in a baseline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// Identifies the finding independently of line numbers
+    pub fingerprint: String,
+    /// File of the finding, as reached from the analyzed path
+    pub file: String,
+    /// Function of the finding, qualified with its contract
+    pub function: String,
+    /// Unvalidated parameter
+    pub parameter: String,
+    /// Rule that classified the finding
+    pub rule: SeverityRule,
+}
+
+/// The findings accepted when the baseline was created
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    /// Layout version, see [`BASELINE_VERSION`]
+    pub version: u32,
+    /// Recorded findings, sorted by file, function and parameter
+    pub entries: Vec<BaselineEntry>,
+}
+
+/// How a run compares with a baseline
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BaselineComparison {
+    /// Path of the baseline file
+    pub path: String,
+    /// Number of findings left out because the baseline records them
+    pub known: usize,
+    /// Recorded findings that no longer occur
+    pub fixed: Vec<BaselineEntry>,
+}
+
+impl Baseline {
+    /// Records every finding of the results
+    pub fn from_results(results: &[AnalysisResult]) -> Self {
+        let mut entries: Vec<BaselineEntry> = results
+            .iter()
+            .flat_map(|result| {
+                result.findings.iter().map(move |finding| BaselineEntry {
+                    fingerprint: finding.fingerprint.clone(),
+                    file: report_path(&result.file_path),
+                    function: result.qualified_name(),
+                    parameter: finding.parameter.clone(),
+                    rule: finding.rule,
+                })
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            (&a.file, &a.function, &a.parameter, &a.fingerprint).cmp(&(
+                &b.file,
+                &b.function,
+                &b.parameter,
+                &b.fingerprint,
+            ))
+        });
+
+        Baseline {
+            version: BASELINE_VERSION,
+            entries,
+        }
+    }
+
+    /// Reads a baseline file
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let text = fs::read_to_string(path).map_err(|e| {
+            format!(
+                "could not read baseline {}: {e} (create it with `construstor baseline create`)",
+                path.display()
+            )
+        })?;
+        let baseline: Baseline = serde_json::from_str(&text)
+            .map_err(|e| format!("invalid baseline {}: {e}", path.display()))?;
+        if baseline.version != BASELINE_VERSION {
+            return Err(format!(
+                "unsupported baseline version {} in {} (expected {BASELINE_VERSION})",
+                baseline.version,
+                path.display()
+            )
+            .into());
+        }
+        Ok(baseline)
+    }
+
+    /// Writes the baseline as pretty-printed JSON
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    /// Starts matching results against the baseline
+    pub fn matcher(&self) -> BaselineMatcher<'_> {
+        let mut remaining = HashMap::new();
+        for entry in &self.entries {
+            *remaining.entry(entry.fingerprint.as_str()).or_insert(0) += 1;
+        }
+        BaselineMatcher {
+            baseline: self,
+            remaining,
+            known: 0,
+        }
+    }
+}
+
+/// Drops recorded findings from results, possibly handed over file by file
+///
+/// A fingerprint recorded `n` times accepts at most `n` findings, so a second copy of a
+/// known finding still counts as new.
+pub struct BaselineMatcher<'a> {
+    baseline: &'a Baseline,
+    remaining: HashMap<&'a str, usize>,
+    known: usize,
+}
+
+impl BaselineMatcher<'_> {
+    /// Removes the findings the baseline records from the results, along with their
+    /// parameters in `missing_validations`
+    pub fn remove_known(&mut self, results: &mut [AnalysisResult]) {
+        for result in results {
+            let known = result.take_findings(|finding| {
+                match self.remaining.get_mut(finding.fingerprint.as_str()) {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        true
+                    }
+                    _ => false,
+                }
+            });
+            self.known += known.len();
+        }
+    }
+
+    /// Finishes matching: how many findings were known and which entries were not seen
+    pub fn finish(self, path: &str) -> BaselineComparison {
+        let mut remaining = self.remaining;
+        let fixed = self
+            .baseline
+            .entries
+            .iter()
+            .filter(
+                |entry| match remaining.get_mut(entry.fingerprint.as_str()) {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        true
+                    }
+                    _ => false,
+                },
+            )
+            .cloned()
+            .collect();
+
+        BaselineComparison {
+            path: path.to_string(),
+            known: self.known,
+            fixed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConstructorAnalyzer;
+    use crate::types::Summary;
+
+    #[test]
+    fn test_baseline_hides_known_findings_across_line_shifts() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let before = "contract Vault {\n    address a;\n    address b;\n    constructor(address _a, address _b) { a = _a; b = _b; }\n}\n";
+        let baseline = Baseline::from_results(&analyzer.analyze_source(before, "Vault.sol", false));
+        assert_eq!(baseline.entries.len(), 2);
+        assert_eq!(baseline.entries[0].function, "Vault.constructor");
+
+        // Lines shift, _b gets a check and a new unchecked function appears
+        let after = "// SPDX-License-Identifier: MIT\n\ncontract Vault {\n    address a;\n    address b;\n    address c;\n    constructor(address _a, address _b) {\n        require(_b != address(0));\n        a = _a; b = _b;\n    }\n    function initialize(address _c) external { c = _c; }\n}\n";
+        let mut results = analyzer.analyze_source(after, "Vault.sol", false);
+        let mut matcher = baseline.matcher();
+        matcher.remove_known(&mut results);
+        let comparison = matcher.finish(BASELINE_FILE_NAME);
+
+        let remaining: Vec<&str> = results
+            .iter()
+            .flat_map(|result| &result.findings)
+            .map(|finding| finding.parameter.as_str())
+            .collect();
+        assert_eq!(remaining, vec!["_c"]);
+        assert!(results[0].missing_validations.is_empty());
+        let summary = Summary::from_results(&results);
+        assert_eq!(summary.overall.fully_validated, 1);
+        assert_eq!(summary.overall.not_validated, 1);
+        assert_eq!(comparison.known, 1);
+        assert_eq!(comparison.fixed.len(), 1);
+        assert_eq!(comparison.fixed[0].parameter, "_b");
+    }
+
+    #[test]
+    fn test_baseline_keeps_findings_of_edited_functions() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let before = "contract Vault {\n    address pool;\n    function setPool(address _pool) external { pool = _pool; }\n}\n";
+        let baseline = Baseline::from_results(&analyzer.analyze_source(before, "Vault.sol", true));
+        assert_eq!(baseline.entries.len(), 1);
+
+        // The function gains a modifier and a statement, which change the finding's rule
+        let after = "contract Vault {\n    address pool;\n    function setPool(address _pool) external onlyOwner {\n        pool = _pool;\n        emit PoolSet(_pool);\n        _pool.call(\"\");\n    }\n}\n";
+        let mut results = analyzer.analyze_source(after, "Vault.sol", true);
+        assert_ne!(results[0].findings[0].rule, baseline.entries[0].rule);
+        let mut matcher = baseline.matcher();
+        matcher.remove_known(&mut results);
+        let comparison = matcher.finish(BASELINE_FILE_NAME);
+
+        assert!(results[0].findings.is_empty());
+        assert_eq!(comparison.known, 1);
+        assert!(comparison.fixed.is_empty());
+    }
+}
@@ -1,54 +1,301 @@
 //! CLI module for handling command-line arguments
 
 use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
 
-use clap::{Arg, Command};
+use clap::parser::ValueSource;
+use clap::{Arg, ArgMatches, Command};
+use serde_json::{Value, json};
+
+use crate::analyzer::AnalyzerOptions;
+use crate::baseline::BASELINE_FILE_NAME;
+use crate::config::{CONFIG_FILE_NAME, ProjectConfig};
+use crate::filter::ResultFilter;
+use crate::output::OutputFormat;
+use crate::policy::{FailOn, FailPolicy};
+use crate::severity::{self, Severity, SeverityRule};
+use crate::types::{FileOrigin, FunctionType, Visibility};
 
 /// CLI configuration
 #[derive(Debug)]
 pub struct CliConfig {
     pub input_path: String,
     pub summary_only: bool,
-    pub json_output: bool,
+    pub show_suppressed: bool,
+    pub format: OutputFormat,
+    pub output_path: Option<String>,
+    pub baseline_path: Option<String>,
     pub all_functions: bool,
+    pub filter: ResultFilter,
+    pub analyzer_options: AnalyzerOptions,
+    pub fail_policy: FailPolicy,
 }
 
-impl CliConfig {
+/// What the command line asks for
+#[derive(Debug)]
+pub enum CliCommand {
+    /// Analyze a file or directory
+    Analyze(Box<CliConfig>),
+    /// Write a commented default configuration file into a directory
+    Init { directory: String, force: bool },
+    /// Record the current findings in a baseline file
+    CreateBaseline(Box<CliConfig>),
+}
+
+impl CliCommand {
     /// Parse command line arguments
     pub fn from_args() -> Result<Self, Box<dyn Error>> {
-        let matches = Command::new("construstor")
-            .version(env!("CARGO_PKG_VERSION"))
-            .author(env!("CARGO_PKG_AUTHORS"))
-            .about("Analyze Solidity smart contracts for zero address validation patterns in constructors, initialize functions, and all functions with address parameters")
-            .arg(
-                Arg::new("input")
-                    .help("Path to Solidity file or directory to analyze")
-                    .required(false)
-                    .index(1),
-            )
-            .arg(
-                Arg::new("summary")
-                    .short('s')
-                    .long("summary")
-                    .help("Show only summary statistics")
-                    .action(clap::ArgAction::SetTrue),
-            )
-            .arg(
-                Arg::new("json")
-                    .short('j')
-                    .long("json")
-                    .help("Output results in JSON format")
-                    .action(clap::ArgAction::SetTrue),
-            )
-            .arg(
-                Arg::new("all-functions")
-                    .short('a')
-                    .long("all-functions")
-                    .help("Analyze all functions with address parameters, not just constructors and initialize functions")
-                    .action(clap::ArgAction::SetTrue),
-            )
-            .get_matches();
+        let matches = command().get_matches();
+
+        match matches.subcommand() {
+            Some(("init", init)) => Ok(CliCommand::Init {
+                directory: init
+                    .get_one::<String>("directory")
+                    .cloned()
+                    .unwrap_or_else(|| ".".to_string()),
+                force: init.get_flag("force"),
+            }),
+            Some(("baseline", baseline)) => match baseline.subcommand() {
+                Some(("create", create)) => Ok(CliCommand::CreateBaseline(Box::new(
+                    CliConfig::from_matches(create)?,
+                ))),
+                _ => Err("expected a baseline subcommand".into()),
+            },
+            _ => Ok(CliCommand::Analyze(Box::new(CliConfig::from_matches(
+                &matches,
+            )?))),
+        }
+    }
+}
 
+/// Builds the command-line interface
+fn command() -> Command {
+    let command = Command::new("construstor")
+        .version(env!("CARGO_PKG_VERSION"))
+        .author(env!("CARGO_PKG_AUTHORS"))
+        .about("Analyze Solidity smart contracts for zero address validation patterns in constructors, initialize functions, and all functions with address parameters");
+    with_analysis_args(command)
+        .subcommand(
+            Command::new("init")
+                .about(format!("Write a commented default {CONFIG_FILE_NAME}"))
+                .arg(
+                    Arg::new("directory")
+                        .help("Directory to write the file into")
+                        .default_value("."),
+                )
+                .arg(
+                    Arg::new("force")
+                        .long("force")
+                        .help("Replace an existing file")
+                        .action(clap::ArgAction::SetTrue),
+                ),
+        )
+        .subcommand(
+            Command::new("baseline")
+                .about("Manage the baseline of accepted findings")
+                .subcommand_required(true)
+                .subcommand(with_analysis_args(Command::new("create").about(format!(
+                    "Record the current findings in a baseline (default: {BASELINE_FILE_NAME})"
+                )))),
+        )
+}
+
+/// Adds the arguments that select and analyze files and shape the report
+fn with_analysis_args(command: Command) -> Command {
+    command
+        .arg(
+            Arg::new("input")
+                .help("Path to Solidity file or directory to analyze")
+                .required(false)
+                .index(1),
+        )
+        .arg(
+            Arg::new("summary")
+                .short('s')
+                .long("summary")
+                .help("Show only summary statistics")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("show-suppressed")
+                .long("show-suppressed")
+                .help("List findings suppressed by construstor-disable comments")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("json")
+                .short('j')
+                .long("json")
+                .help("Output results in JSON format (same as --format json)")
+                .conflicts_with("format")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("format")
+                .short('f')
+                .long("format")
+                .value_name("FORMAT")
+                .help("Output format")
+                .value_parser(OutputFormat::NAMES)
+                .default_value("text"),
+        )
+        .arg(
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_name("FILE")
+                .help("Write the report to FILE instead of standard output (not available for text)"),
+        )
+        .arg(
+            Arg::new("json-include-code")
+                .long("json-include-code")
+                .help("Include each function's source, surrounding lines and unvalidated parameter offsets in JSON and NDJSON output")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("context-lines")
+                .long("context-lines")
+                .value_name("N")
+                .help("Lines of context around each function with --json-include-code [default: 3]")
+                .requires("json-include-code")
+                .value_parser(clap::value_parser!(usize)),
+        )
+        .arg(
+            Arg::new("all-functions")
+                .short('a')
+                .long("all-functions")
+                .help("Analyze all functions with address parameters, not just constructors and initialize functions")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("kind")
+                .short('k')
+                .long("kind")
+                .help("Only report these function kinds (comma-separated)")
+                .value_delimiter(',')
+                .value_parser(FunctionType::KINDS),
+        )
+        .arg(
+            Arg::new("visibility")
+                .long("visibility")
+                .help("Only report functions with these visibilities (comma-separated)")
+                .value_delimiter(',')
+                .value_parser(Visibility::NAMES),
+        )
+        .arg(
+            Arg::new("origin")
+                .long("origin")
+                .help("Only report functions from files of these origins (comma-separated)")
+                .value_delimiter(',')
+                .value_parser(FileOrigin::NAMES),
+        )
+        .arg(
+            Arg::new("skip-view")
+                .long("skip-view")
+                .help("Skip view and pure functions")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("access-modifier")
+                .long("access-modifier")
+                .value_name("NAME")
+                .help("Treat these modifiers as access control, in addition to the defaults (comma-separated)")
+                .value_delimiter(',')
+                .action(clap::ArgAction::Append),
+        )
+        .arg(
+            Arg::new("access-check")
+                .long("access-check")
+                .value_name("REGEX")
+                .help("Treat function bodies matching this regex as caller-restricted, in addition to the defaults")
+                .action(clap::ArgAction::Append),
+        )
+        .arg(
+            Arg::new("min-severity")
+                .long("min-severity")
+                .value_name("SEVERITY")
+                .help("Only report functions with a finding at or above this severity")
+                .value_parser(Severity::NAMES),
+        )
+        .arg(
+            Arg::new("severity")
+                .long("severity")
+                .value_name("RULE=SEVERITY")
+                .help("Override the severity assigned by a rule, e.g. call-target=critical")
+                .value_parser(severity::parse_override)
+                .action(clap::ArgAction::Append),
+        )
+        .arg(
+            Arg::new("fail-on")
+                .long("fail-on")
+                .value_name("SEVERITY|any|none")
+                .help("Exit with status 1 when findings at or above this severity are present [default: any]")
+                .value_parser(|value: &str| value.parse::<FailOn>()),
+        )
+        .arg(
+            Arg::new("max-findings")
+                .long("max-findings")
+                .value_name("N")
+                .help("Number of failing findings tolerated before exiting with status 1 [default: 0]")
+                .value_parser(clap::value_parser!(usize)),
+        )
+        .arg(
+            Arg::new("config")
+                .short('c')
+                .long("config")
+                .value_name("FILE")
+                .help(format!("Read settings from FILE instead of the nearest {CONFIG_FILE_NAME}")),
+        )
+        .arg(
+            Arg::new("include")
+                .long("include")
+                .value_name("GLOB")
+                .help("Only analyze files matching these globs, relative to the input directory (replaces the configured list)")
+                .action(clap::ArgAction::Append),
+        )
+        .arg(
+            Arg::new("exclude")
+                .long("exclude")
+                .value_name("GLOB")
+                .help("Skip files and directories matching these globs, relative to the input directory")
+                .action(clap::ArgAction::Append),
+        )
+        .arg(
+            Arg::new("no-default-excludes")
+                .long("no-default-excludes")
+                .help("Also analyze node_modules and the lib, test and tests directories")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("no-ignore-files")
+                .long("no-ignore-files")
+                .help("Do not honor .gitignore, .ignore and .construstorignore files")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("validator")
+                .long("validator")
+                .value_name("NAME")
+                .help("Treat arguments passed to these functions as checked against address(0) (comma-separated)")
+                .value_delimiter(',')
+                .action(clap::ArgAction::Append),
+        )
+        .arg(
+            Arg::new("baseline")
+                .long("baseline")
+                .value_name("FILE")
+                .help("Only report findings not recorded in this baseline, and list the recorded ones that were fixed"),
+        )
+}
+
+impl CliConfig {
+    /// Builds the configuration from parsed arguments and the project configuration file
+    ///
+    /// Values given on the command line take precedence over the file; lists of
+    /// access-control modifiers, caller checks, validators, excludes and severity overrides
+    /// are merged. Globs and paths from the file are relative to its directory.
+    fn from_matches(matches: &ArgMatches) -> Result<Self, Box<dyn Error>> {
         let input_path = if let Some(path) = matches.get_one::<String>("input") {
             path.clone()
         } else {
@@ -59,11 +306,433 @@ impl CliConfig {
                 .interact_text()?
         };
 
+        let config_path = match matches.get_one::<String>("config") {
+            Some(path) => Some(PathBuf::from(path)),
+            None => ProjectConfig::discover(Path::new(&input_path)),
+        };
+        let project = match &config_path {
+            Some(path) => ProjectConfig::load(path)?,
+            None => ProjectConfig::default(),
+        };
+        let config_dir = config_path.as_deref().and_then(Path::parent);
+        // Globs from the file are anchored to its directory as absolute globs, since the
+        // analyzed directory may be below it
+        let config_globs = |globs: &[String]| -> Result<Vec<String>, Box<dyn Error>> {
+            let Some(path) = &config_path else {
+                return Ok(globs.to_vec());
+            };
+            let directory = fs::canonicalize(path)?
+                .parent()
+                .map(|dir| globset::escape(&dir.to_string_lossy()))
+                .unwrap_or_default();
+            Ok(globs
+                .iter()
+                .map(|glob| {
+                    if Path::new(glob).is_absolute() {
+                        glob.clone()
+                    } else {
+                        format!("{directory}/{glob}")
+                    }
+                })
+                .collect())
+        };
+        let config_file_path = |path: &String| match config_dir {
+            Some(directory) => directory.join(path).to_string_lossy().into_owned(),
+            None => path.clone(),
+        };
+
+        let function_kinds = match matches.get_many::<String>("kind") {
+            Some(kinds) => kinds.cloned().collect(),
+            None => config_names(
+                "checks.kinds",
+                project.checks.kinds.clone().unwrap_or_default(),
+                &FunctionType::KINDS,
+            )?,
+        };
+
+        // Asking for any kind beyond constructors and initializers implies --all-functions
+        let all_functions = from_command_line(matches, "all-functions")
+            .then_some(true)
+            .or(project.checks.all_functions)
+            .unwrap_or(false)
+            || function_kinds
+                .iter()
+                .any(|kind| kind != "constructor" && kind != "initialize");
+
+        let visibilities = match matches.get_many::<String>("visibility") {
+            Some(names) => names.cloned().collect(),
+            None => config_names(
+                "checks.visibility",
+                project.checks.visibility.clone().unwrap_or_default(),
+                &Visibility::NAMES,
+            )?,
+        };
+
+        let origins = match matches.get_many::<String>("origin") {
+            Some(names) => names.cloned().collect(),
+            None => config_names(
+                "checks.origin",
+                project.checks.origin.clone().unwrap_or_default(),
+                &FileOrigin::NAMES,
+            )?,
+        };
+
+        let mut analyzer_options = AnalyzerOptions {
+            validators: project.validators.clone(),
+            include: match matches.get_many::<String>("include") {
+                Some(globs) => globs.cloned().collect(),
+                None => config_globs(&project.include)?,
+            },
+            exclude: config_globs(&project.exclude)?,
+            skip_dependencies: !matches.get_flag("no-default-excludes")
+                && project.skip_dependencies.unwrap_or(true),
+            use_ignore_files: !matches.get_flag("no-ignore-files")
+                && project.use_ignore_files.unwrap_or(true),
+            // Paths in fingerprints are relative to the project, wherever it is checked out
+            fingerprint_root: config_dir.map(|directory| {
+                if directory.as_os_str().is_empty() {
+                    PathBuf::from(".")
+                } else {
+                    directory.to_path_buf()
+                }
+            }),
+            ..AnalyzerOptions::default()
+        };
+        if let Some(globs) = matches.get_many::<String>("exclude") {
+            analyzer_options.exclude.extend(globs.cloned());
+        }
+        analyzer_options
+            .access_control_modifiers
+            .extend(project.access_control.modifiers.iter().cloned());
+        analyzer_options
+            .caller_checks
+            .extend(project.access_control.checks.iter().cloned());
+        analyzer_options
+            .severity_overrides
+            .extend(&project.severity);
+        if let Some(names) = matches.get_many::<String>("access-modifier") {
+            analyzer_options
+                .access_control_modifiers
+                .extend(names.cloned());
+        }
+        if let Some(patterns) = matches.get_many::<String>("access-check") {
+            analyzer_options.caller_checks.extend(patterns.cloned());
+        }
+        if let Some(names) = matches.get_many::<String>("validator") {
+            analyzer_options.validators.extend(names.cloned());
+        }
+        if let Some(overrides) = matches.get_many::<(SeverityRule, Severity)>("severity") {
+            analyzer_options
+                .severity_overrides
+                .extend(overrides.copied());
+        }
+
+        let format = if matches.get_flag("json") {
+            OutputFormat::Json
+        } else if from_command_line(matches, "format") {
+            matches
+                .get_one::<String>("format")
+                .map_or(Ok(OutputFormat::Text), |name| name.parse())?
+        } else {
+            project
+                .output
+                .format
+                .as_deref()
+                .map_or(Ok(OutputFormat::Text), str::parse)
+                .map_err(|e| format!("invalid output.format in configuration: {e}"))?
+        };
+        if matches.get_flag("json-include-code") {
+            if !matches!(format, OutputFormat::Json | OutputFormat::Ndjson) {
+                return Err("--json-include-code requires --format json or ndjson".into());
+            }
+            analyzer_options.source_context = Some(
+                matches
+                    .get_one::<usize>("context-lines")
+                    .copied()
+                    .unwrap_or(3),
+            );
+        }
+        let output_path = matches
+            .get_one::<String>("output")
+            .cloned()
+            .or_else(|| project.output.path.as_ref().map(config_file_path));
+        if output_path.is_some() && format == OutputFormat::Text {
+            return Err("--output requires a --format other than text".into());
+        }
+
+        let fail_on = match matches.get_one::<FailOn>("fail-on") {
+            Some(fail_on) => *fail_on,
+            None => project
+                .fail
+                .on
+                .as_deref()
+                .map_or(Ok(FailOn::default()), str::parse)
+                .map_err(|e| format!("invalid fail.on in configuration: {e}"))?,
+        };
+
         Ok(CliConfig {
             input_path,
             summary_only: matches.get_flag("summary"),
-            json_output: matches.get_flag("json"),
-            all_functions: matches.get_flag("all-functions"),
+            show_suppressed: from_command_line(matches, "show-suppressed")
+                .then_some(true)
+                .or(project.output.show_suppressed)
+                .unwrap_or(false),
+            format,
+            output_path,
+            baseline_path: matches
+                .get_one::<String>("baseline")
+                .cloned()
+                .or_else(|| project.baseline.as_ref().map(config_file_path)),
+            all_functions,
+            filter: ResultFilter {
+                function_kinds,
+                visibilities,
+                skip_view: from_command_line(matches, "skip-view")
+                    .then_some(true)
+                    .or(project.checks.skip_view)
+                    .unwrap_or(false),
+                min_severity: match matches.get_one::<String>("min-severity") {
+                    Some(name) => Some(name.parse()?),
+                    None => project.checks.min_severity,
+                },
+                origins,
+            },
+            analyzer_options,
+            fail_policy: FailPolicy {
+                fail_on,
+                max_findings: matches
+                    .get_one::<usize>("max-findings")
+                    .copied()
+                    .or(project.fail.max_findings)
+                    .unwrap_or(0),
+            },
+        })
+    }
+
+    /// Describes the options that shape the results, for reports that record them
+    pub fn report_options(&self) -> Value {
+        let severity_overrides: serde_json::Map<String, Value> = SeverityRule::ALL
+            .iter()
+            .filter_map(|rule| {
+                let severity = self.analyzer_options.severity_overrides.get(rule)?;
+                Some((rule.id().to_string(), json!(severity)))
+            })
+            .collect();
+
+        json!({
+            "all_functions": self.all_functions,
+            "kinds": self.filter.function_kinds,
+            "visibilities": self.filter.visibilities,
+            "skip_view": self.filter.skip_view,
+            "min_severity": self.filter.min_severity,
+            "origins": self.filter.origins,
+            "show_suppressed": self.show_suppressed,
+            "baseline": self.baseline_path,
+            "access_control_modifiers": self.analyzer_options.access_control_modifiers,
+            "caller_checks": self.analyzer_options.caller_checks,
+            "validators": self.analyzer_options.validators,
+            "include": self.analyzer_options.include,
+            "exclude": self.analyzer_options.exclude,
+            "skip_dependencies": self.analyzer_options.skip_dependencies,
+            "use_ignore_files": self.analyzer_options.use_ignore_files,
+            "severity_overrides": severity_overrides,
+            "include_code": self.analyzer_options.source_context.is_some(),
+            "context_lines": self.analyzer_options.source_context,
+            "fail_on": self.fail_policy.fail_on.to_string(),
+            "max_findings": self.fail_policy.max_findings,
         })
     }
 }
+
+/// Returns true if the argument was given on the command line rather than defaulted
+fn from_command_line(matches: &ArgMatches, id: &str) -> bool {
+    matches.value_source(id) == Some(ValueSource::CommandLine)
+}
+
+/// Checks that every name read from the configuration file is one of `allowed`
+fn config_names(
+    key: &str,
+    names: Vec<String>,
+    allowed: &[&str],
+) -> Result<Vec<String>, Box<dyn Error>> {
+    match names.iter().find(|name| !allowed.contains(&name.as_str())) {
+        Some(name) => Err(format!(
+            "invalid {key} '{name}' in configuration, expected one of: {}",
+            allowed.join(", ")
+        )
+        .into()),
+        None => Ok(names),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConstructorAnalyzer;
+
+    /// Parses an analysis command line with `construstor.toml` written into `root`
+    fn parse(root: &Path, config: &str, args: &[&str]) -> CliConfig {
+        fs::write(root.join(CONFIG_FILE_NAME), config).unwrap();
+        let matches = command()
+            .try_get_matches_from(["construstor"].iter().chain(args))
+            .unwrap();
+        CliConfig::from_matches(&matches).unwrap()
+    }
+
+    #[test]
+    fn test_command_line_overrides_configuration() {
+        let root = tempfile::tempdir().unwrap();
+        let input = root.path().to_str().unwrap();
+        let config = r#"
+            [checks]
+            skip_view = true
+            min_severity = "medium"
+            [output]
+            format = "sarif"
+            [fail]
+            on = "high"
+            max_findings = 2
+        "#;
+
+        let from_file = parse(root.path(), config, &[input]);
+        assert_eq!(from_file.format, OutputFormat::Sarif);
+        assert!(from_file.filter.skip_view);
+        assert_eq!(from_file.filter.min_severity, Some(Severity::Medium));
+        assert_eq!(
+            from_file.fail_policy.fail_on,
+            FailOn::Severity(Severity::High)
+        );
+        assert_eq!(from_file.fail_policy.max_findings, 2);
+
+        let overridden = parse(
+            root.path(),
+            config,
+            &[
+                input,
+                "--format",
+                "json",
+                "--min-severity",
+                "critical",
+                "--fail-on",
+                "none",
+                "--max-findings",
+                "0",
+            ],
+        );
+        assert_eq!(overridden.format, OutputFormat::Json);
+        assert!(overridden.filter.skip_view);
+        assert_eq!(overridden.filter.min_severity, Some(Severity::Critical));
+        assert_eq!(overridden.fail_policy.fail_on, FailOn::None);
+        assert_eq!(overridden.fail_policy.max_findings, 0);
+    }
+
+    #[test]
+    fn test_lists_are_merged_and_include_is_replaced() {
+        let root = tempfile::tempdir().unwrap();
+        let input = root.path().to_str().unwrap();
+        let directory = globset::escape(&fs::canonicalize(root.path()).unwrap().to_string_lossy());
+        let config = r#"
+            include = ["src/**"]
+            exclude = ["src/mocks/**"]
+            validators = ["_requireNonZero"]
+            [access_control]
+            modifiers = ["onlyKeeper"]
+            [severity]
+            call-target = "critical"
+        "#;
+
+        let from_file = parse(root.path(), config, &[input]);
+        assert_eq!(
+            from_file.analyzer_options.include,
+            vec![format!("{directory}/src/**")]
+        );
+
+        let cli = parse(
+            root.path(),
+            config,
+            &[
+                input,
+                "--include",
+                "contracts/**",
+                "--exclude",
+                "**/Mock*.sol",
+                "--validator",
+                "_check",
+                "--access-modifier",
+                "onlyGuardian",
+                "--severity",
+                "token-recipient=low",
+            ],
+        );
+        let options = &cli.analyzer_options;
+        assert_eq!(options.include, vec!["contracts/**"]);
+        assert_eq!(
+            options.exclude,
+            vec![
+                format!("{directory}/src/mocks/**"),
+                "**/Mock*.sol".to_string()
+            ]
+        );
+        assert_eq!(options.validators, vec!["_requireNonZero", "_check"]);
+        assert!(
+            options
+                .access_control_modifiers
+                .ends_with(&["onlyKeeper".to_string(), "onlyGuardian".to_string()])
+        );
+        assert_eq!(
+            options.severity_overrides.get(&SeverityRule::CallTarget),
+            Some(&Severity::Critical)
+        );
+        assert_eq!(
+            options
+                .severity_overrides
+                .get(&SeverityRule::TokenRecipient),
+            Some(&Severity::Low)
+        );
+    }
+
+    #[test]
+    fn test_kind_implies_all_functions() {
+        let root = tempfile::tempdir().unwrap();
+        let input = root.path().to_str().unwrap();
+
+        assert!(!parse(root.path(), "", &[input]).all_functions);
+        assert!(!parse(root.path(), "", &[input, "--kind", "initialize"]).all_functions);
+        assert!(parse(root.path(), "", &[input, "--kind", "receive"]).all_functions);
+        assert!(parse(root.path(), "[checks]\nkinds = [\"function\"]\n", &[input]).all_functions);
+    }
+
+    #[test]
+    fn test_configuration_paths_are_relative_to_its_directory() {
+        let root = tempfile::tempdir().unwrap();
+        let src = root.path().join("src");
+        fs::create_dir_all(src.join("mocks")).unwrap();
+        let contract = "contract C { constructor(address _a) { a = _a; } }";
+        fs::write(src.join("Vault.sol"), contract).unwrap();
+        fs::write(src.join("mocks/Mock.sol"), contract).unwrap();
+        let config = r#"
+            exclude = ["src/mocks/**"]
+            baseline = "audit/baseline.json"
+            [output]
+            format = "json"
+            path = "reports/construstor.json"
+        "#;
+
+        let cli = parse(root.path(), config, &[src.to_str().unwrap()]);
+
+        let analyzer = ConstructorAnalyzer::with_options(cli.analyzer_options).unwrap();
+        let results = analyzer.analyze_path(&cli.input_path, false).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].file_path.ends_with("Vault.sol"));
+
+        let directory = fs::canonicalize(root.path()).unwrap();
+        assert_eq!(
+            cli.baseline_path.map(PathBuf::from),
+            Some(directory.join("audit/baseline.json"))
+        );
+        assert_eq!(
+            cli.output_path.map(PathBuf::from),
+            Some(directory.join("reports/construstor.json"))
+        );
+    }
+}
@@ -0,0 +1,242 @@
+//! Project configuration read from `construstor.toml`
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::severity::{Severity, SeverityRule};
+
+/// Name of the configuration file looked up from the analyzed path upwards
+pub const CONFIG_FILE_NAME: &str = "construstor.toml";
+
+/// Commented configuration written by `construstor init`
+pub const DEFAULT_CONFIG: &str = r#"# construstor configuration
+#
+# Found by walking up from the analyzed path. Command-line flags override these settings.
+# Globs and paths are relative to the directory of this file.
+
+# Globs of files to analyze and of files or directories to skip. An empty include list
+# analyzes every .sol file.
+include = []
+exclude = []
+# Skip node_modules, and lib, test and tests at the root of the analyzed directory
+skip_dependencies = true
+# Skip files matched by .gitignore, .ignore and .construstorignore
+use_ignore_files = true
+
+# Functions passed to a validator count as checked against address(0).
+# validators = ["_requireNonZeroAddress", "Errors.verifyNotZero"]
+validators = []
+
+# Only report findings not recorded in this baseline (see `construstor baseline create`)
+# baseline = "construstor-baseline.json"
+
+[checks]
+# Analyze every function with address parameters, not only constructors and initializers
+all_functions = false
+# Only report these kinds: constructor, initialize, function, modifier, free-function, library-function,
+# fallback, receive
+# kinds = ["constructor", "initialize", "function"]
+# Only report functions with these visibilities: external, public, internal, private
+# visibility = ["external", "public"]
+# Skip view and pure functions
+skip_view = false
+# Only report findings at or above this severity, and the functions that have one
+# min_severity = "medium"
+# Only report functions from these file origins: first-party, dependency, test-script
+# origin = ["first-party"]
+
+[access_control]
+# Modifiers and body patterns (regexes) that make a function trusted-caller only,
+# in addition to the built-in ones such as onlyOwner and msg.sender checks
+modifiers = []
+checks = []
+
+[severity]
+# Replace the default severity of a rule
+# call-target = "critical"
+# access-controlled-setter = "info"
+
+[output]
+# text, json, sarif, junit, markdown, html, csv, github, gitlab, compact or ndjson
+format = "text"
+# Write the report to a file instead of standard output (not for text)
+# path = "construstor-report.json"
+# List findings suppressed by construstor-disable comments (text, SARIF, Markdown and HTML)
+show_suppressed = false
+
+[fail]
+# Exit with status 1 on findings at or above a severity, on any finding, or never: any, none, info..critical
+on = "any"
+# Number of failing findings tolerated
+max_findings = 0
+"#;
+
+/// Settings from a `construstor.toml` file; unset values fall back to the defaults
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectConfig {
+    /// Globs of files to analyze, relative to the directory of the file
+    pub include: Vec<String>,
+    /// Globs of files and directories to skip, relative to the directory of the file
+    pub exclude: Vec<String>,
+    /// Skip dependency and test directories
+    pub skip_dependencies: Option<bool>,
+    /// Honor `.gitignore`, `.ignore` and `.construstorignore` files
+    pub use_ignore_files: Option<bool>,
+    /// Functions that validate the addresses passed to them
+    pub validators: Vec<String>,
+    /// Baseline file of accepted findings, relative to the directory of the file
+    pub baseline: Option<String>,
+    /// Which functions are analyzed and reported
+    pub checks: ChecksConfig,
+    /// Additional access-control modifiers and caller checks
+    pub access_control: AccessControlConfig,
+    /// Severities that replace the default of a rule
+    pub severity: HashMap<SeverityRule, Severity>,
+    /// Report format and destination
+    pub output: OutputConfig,
+    /// Failure policy
+    pub fail: FailConfig,
+}
+
+/// The `[checks]` table
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChecksConfig {
+    pub all_functions: Option<bool>,
+    pub kinds: Option<Vec<String>>,
+    pub visibility: Option<Vec<String>>,
+    pub skip_view: Option<bool>,
+    pub min_severity: Option<Severity>,
+    pub origin: Option<Vec<String>>,
+}
+
+/// The `[access_control]` table
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AccessControlConfig {
+    pub modifiers: Vec<String>,
+    pub checks: Vec<String>,
+}
+
+/// The `[output]` table
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutputConfig {
+    pub format: Option<String>,
+    pub path: Option<String>,
+    pub show_suppressed: Option<bool>,
+}
+
+/// The `[fail]` table
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FailConfig {
+    pub on: Option<String>,
+    pub max_findings: Option<usize>,
+}
+
+impl ProjectConfig {
+    /// Parses configuration from TOML text
+    pub fn parse(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+
+    /// Reads and parses a configuration file
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let text = fs::read_to_string(path)?;
+        Self::parse(&text).map_err(|e| format!("{}: {e}", path.display()).into())
+    }
+
+    /// Finds the nearest configuration file in the directory of `input_path` or one of its
+    /// ancestors
+    pub fn discover(input_path: &Path) -> Option<PathBuf> {
+        let start = fs::canonicalize(input_path).ok()?;
+        let directory = if start.is_dir() {
+            start.as_path()
+        } else {
+            start.parent()?
+        };
+        directory
+            .ancestors()
+            .map(|dir| dir.join(CONFIG_FILE_NAME))
+            .find(|candidate| candidate.is_file())
+    }
+}
+
+/// Writes [`DEFAULT_CONFIG`] into `directory`, refusing to replace an existing file
+/// unless `force` is set
+pub fn write_default(directory: &Path, force: bool) -> Result<PathBuf, Box<dyn Error>> {
+    let path = directory.join(CONFIG_FILE_NAME);
+    if path.exists() && !force {
+        return Err(format!(
+            "{} already exists (use --force to replace it)",
+            path.display()
+        )
+        .into());
+    }
+    fs::write(&path, DEFAULT_CONFIG)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_parses() {
+        let config = ProjectConfig::parse(DEFAULT_CONFIG).unwrap();
+
+        assert_eq!(config.checks.all_functions, Some(false));
+        assert_eq!(config.skip_dependencies, Some(true));
+        assert_eq!(config.output.format.as_deref(), Some("text"));
+        assert_eq!(config.fail.on.as_deref(), Some("any"));
+        assert!(config.severity.is_empty());
+    }
+
+    #[test]
+    fn test_parse_sections_and_reject_unknown_keys() {
+        let config = ProjectConfig::parse(
+            r#"
+            exclude = ["**/mocks/**"]
+            validators = ["_requireNonZero"]
+            [checks]
+            kinds = ["function"]
+            [severity]
+            call-target = "critical"
+            [fail]
+            on = "high"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.exclude, vec!["**/mocks/**"]);
+        assert_eq!(config.checks.kinds, Some(vec!["function".to_string()]));
+        assert_eq!(
+            config.severity.get(&SeverityRule::CallTarget),
+            Some(&Severity::Critical)
+        );
+        assert!(ProjectConfig::parse("[checks]\nall_function = true\n").is_err());
+    }
+
+    #[test]
+    fn test_discover_walks_up_from_input() {
+        let root = tempfile::tempdir().unwrap();
+        let nested = root.path().join("src/core");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(nested.join("Vault.sol"), "").unwrap();
+        assert_eq!(ProjectConfig::discover(&nested.join("Vault.sol")), None);
+
+        let written = write_default(root.path(), false).unwrap();
+        assert!(write_default(root.path(), false).is_err());
+
+        assert_eq!(
+            ProjectConfig::discover(&nested.join("Vault.sol")),
+            Some(fs::canonicalize(written).unwrap())
+        );
+    }
+}
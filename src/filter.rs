@@ -0,0 +1,141 @@
+//! Filtering of analysis results based on user-selected criteria
+
+use crate::severity::Severity;
+use crate::types::{AnalysisResult, StateMutability};
+
+/// Criteria a result must meet to be reported
+#[derive(Debug, Clone, Default)]
+pub struct ResultFilter {
+    /// Function kinds to keep (see [`FunctionType::KINDS`]); empty keeps all
+    ///
+    /// [`FunctionType::KINDS`]: crate::types::FunctionType::KINDS
+    pub function_kinds: Vec<String>,
+    /// Visibilities to keep (see [`Visibility::NAMES`]); empty keeps all
+    ///
+    /// Results without a visibility, such as constructors and modifiers, are always kept.
+    ///
+    /// [`Visibility::NAMES`]: crate::types::Visibility::NAMES
+    pub visibilities: Vec<String>,
+    /// Drop `view` and `pure` functions
+    pub skip_view: bool,
+    /// Only keep findings at or above this severity, and the results that have one left
+    pub min_severity: Option<Severity>,
+    /// File origins to keep (see [`FileOrigin::NAMES`]); empty keeps all
+    ///
+    /// [`FileOrigin::NAMES`]: crate::types::FileOrigin::NAMES
+    pub origins: Vec<String>,
+}
+
+impl ResultFilter {
+    /// Returns true if the result passes every criterion
+    pub fn matches(&self, result: &AnalysisResult) -> bool {
+        let kind_matches = self.function_kinds.is_empty()
+            || self
+                .function_kinds
+                .iter()
+                .any(|kind| kind == result.function_type.kind());
+
+        let visibility_matches = match result.visibility {
+            Some(visibility) if !self.visibilities.is_empty() => self
+                .visibilities
+                .iter()
+                .any(|name| name == visibility.as_str()),
+            _ => true,
+        };
+
+        let mutability_matches = !self.skip_view
+            || !matches!(
+                result.mutability,
+                StateMutability::View | StateMutability::Pure
+            );
+
+        let severity_matches = self
+            .min_severity
+            .is_none_or(|min| result.max_severity().is_some_and(|max| max >= min));
+
+        let origin_matches = self.origins.is_empty()
+            || self
+                .origins
+                .iter()
+                .any(|name| name == result.origin.as_str());
+
+        kind_matches
+            && visibility_matches
+            && mutability_matches
+            && severity_matches
+            && origin_matches
+    }
+
+    /// Removes findings below the minimum severity, then results that do not pass the filter
+    pub fn apply(&self, results: &mut Vec<AnalysisResult>) {
+        if let Some(min) = self.min_severity {
+            for result in results.iter_mut() {
+                result.take_findings(|finding| finding.severity < min);
+            }
+        }
+        results.retain(|result| self.matches(result));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConstructorAnalyzer;
+
+    const SOURCE: &str = r#"
+        contract Vault {
+            function execute(address target, address other) external {
+                target.delegatecall("");
+            }
+            function peek(address who) external view returns (uint256) { return who.balance; }
+            modifier only(address who) { require(msg.sender == who); _; }
+        }
+    "#;
+
+    fn analyze() -> Vec<AnalysisResult> {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        analyzer.analyze_source(SOURCE, "Vault.sol", true)
+    }
+
+    #[test]
+    fn test_min_severity_drops_lower_findings_of_kept_functions() {
+        let filter = ResultFilter {
+            min_severity: Some(Severity::Critical),
+            ..ResultFilter::default()
+        };
+        let mut results = analyze();
+        filter.apply(&mut results);
+
+        assert_eq!(results.len(), 1);
+        let parameters: Vec<&str> = results[0]
+            .findings
+            .iter()
+            .map(|finding| finding.parameter.as_str())
+            .collect();
+        assert_eq!(parameters, vec!["target"]);
+        assert_eq!(results[0].missing_validations, vec!["target"]);
+    }
+
+    #[test]
+    fn test_kind_visibility_and_view_criteria() {
+        let mut results = analyze();
+        ResultFilter {
+            function_kinds: vec!["function".to_string()],
+            skip_view: true,
+            ..ResultFilter::default()
+        }
+        .apply(&mut results);
+        let names: Vec<&str> = results.iter().map(|r| r.function_type.name()).collect();
+        assert_eq!(names, vec!["execute"]);
+
+        // Modifiers have no visibility and are kept by a visibility filter
+        let mut results = analyze();
+        ResultFilter {
+            visibilities: vec!["internal".to_string()],
+            ..ResultFilter::default()
+        }
+        .apply(&mut results);
+        let names: Vec<&str> = results.iter().map(|r| r.function_type.name()).collect();
+        assert_eq!(names, vec!["only"]);
+    }
+}
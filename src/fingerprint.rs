@@ -0,0 +1,142 @@
+//! Stable identifiers for findings, shared by every report and by baselines
+//!
+//! A fingerprint hashes the file path, the enclosing contract, the canonical signature of
+//! the function, the parameter name and the check. Line numbers and the rule are left out,
+//! so moving code around or editing the function keeps the fingerprint, while renaming a
+//! parameter or changing the function signature gives a new one.
+
+use crate::analyzer::mask_comments_and_strings;
+use crate::output::report_path;
+use crate::suppression::CHECK_NAME;
+
+/// Data locations, which are not part of a parameter's type in a signature
+const DATA_LOCATIONS: [&str; 3] = ["memory", "storage", "calldata"];
+
+/// Hashes text with 64-bit FNV-1a, which unlike std's hasher is stable across releases
+pub fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Builds the fingerprint of the finding for `parameter` in a function
+///
+/// `path` is the file relative to a fixed root, such as the directory of the configuration
+/// file, so that it does not depend on where the project is checked out; `signature` comes
+/// from [`canonical_signature`]. The rule is not part of it, since it follows from how the
+/// body uses the parameter and would change with unrelated edits.
+pub fn finding_fingerprint(
+    path: &str,
+    contract: Option<&str>,
+    signature: &str,
+    parameter: &str,
+) -> String {
+    let key = format!(
+        "{}|{}|{}|{}|{}",
+        report_path(path),
+        contract.unwrap_or_default(),
+        signature,
+        parameter,
+        CHECK_NAME
+    );
+    format!("{:016x}", fnv1a(&key))
+}
+
+/// Returns `name(type,...)` with the parameter types of a parameter list as in an ABI
+/// signature: without names, data locations or `payable`, and with `uint` and `int`
+/// spelled out
+pub fn canonical_signature(name: &str, arguments: &str) -> String {
+    let masked = mask_comments_and_strings(arguments);
+    let types: Vec<String> = split_parameters(&masked)
+        .into_iter()
+        .filter_map(canonical_type)
+        .collect();
+    format!("{name}({})", types.join(","))
+}
+
+/// Splits a parameter list on the commas that are not nested in parentheses
+fn split_parameters(arguments: &str) -> Vec<&str> {
+    let mut parameters = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (index, c) in arguments.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parameters.push(&arguments[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parameters.push(&arguments[start..]);
+    parameters
+}
+
+/// Reduces one parameter declaration to its canonical type, or `None` if it is empty
+fn canonical_type(parameter: &str) -> Option<String> {
+    // Gather array brackets onto their type, so that `uint[] memory` splits like `uint[]`
+    let compact = parameter
+        .replace(" [", "[")
+        .replace("[ ", "[")
+        .replace(" ]", "]");
+    let mut tokens: Vec<&str> = compact
+        .split_whitespace()
+        .filter(|token| !DATA_LOCATIONS.contains(token))
+        .collect();
+    if tokens.len() > 1 && *tokens.last()? != "payable" {
+        // The last word of a named parameter is its name
+        tokens.pop();
+    }
+    tokens.retain(|token| *token != "payable");
+    let ty = tokens.concat();
+    if ty.is_empty() {
+        return None;
+    }
+
+    let (base, arrays) = ty.split_at(ty.find('[').unwrap_or(ty.len()));
+    let base = match base {
+        "uint" => "uint256",
+        "int" => "int256",
+        "byte" => "bytes1",
+        base => base,
+    };
+    Some(format!("{base}{arrays}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_signature_keeps_only_types() {
+        assert_eq!(
+            canonical_signature(
+                "setPool",
+                "address payable _pool, uint [] memory _ids, /* owner */ Types.Config calldata cfg, address"
+            ),
+            "setPool(address,uint256[],Types.Config,address)"
+        );
+        assert_eq!(canonical_signature("constructor", " "), "constructor()");
+    }
+
+    #[test]
+    fn test_finding_fingerprint_ignores_path_prefix_and_depends_on_signature() {
+        let fingerprint =
+            |path, signature| finding_fingerprint(path, Some("Vault"), signature, "_owner");
+
+        assert_eq!(
+            fingerprint("./src/Vault.sol", "constructor(address)"),
+            fingerprint("src/Vault.sol", "constructor(address)")
+        );
+        assert_ne!(
+            fingerprint("src/Vault.sol", "constructor(address)"),
+            fingerprint("src/Vault.sol", "constructor(address,uint256)")
+        );
+        assert_eq!(
+            fingerprint("src/Vault.sol", "constructor(address)").len(),
+            16
+        );
+    }
+}
@@ -4,38 +4,117 @@
 //! patterns in constructors, initialize functions, and all functions with address parameters.
 
 pub mod analyzer;
+pub mod baseline;
 pub mod cli;
+pub mod config;
+pub mod filter;
+pub mod fingerprint;
+pub mod natspec;
+pub mod output;
+pub mod policy;
 pub mod printer;
+pub mod severity;
+pub mod suppression;
 pub mod types;
 
 use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::ops::ControlFlow;
+use std::path::Path;
 
-pub use analyzer::ConstructorAnalyzer;
-pub use cli::CliConfig;
+pub use analyzer::{AnalyzerOptions, ConstructorAnalyzer};
+pub use baseline::{BASELINE_FILE_NAME, Baseline, BaselineComparison};
+pub use cli::{CliCommand, CliConfig};
 use colored::*;
+pub use config::ProjectConfig;
+pub use filter::ResultFilter;
+use output::ndjson::NdjsonWriter;
+pub use output::{OutputFormat, ReportContext};
+pub use policy::{EXIT_CLEAN, EXIT_ERROR, EXIT_FINDINGS, FailOn, FailPolicy};
 pub use printer::ResultPrinter;
+pub use severity::{Severity, SeverityRule};
 pub use types::*;
 
 /// Main application logic
-pub fn run(config: CliConfig) -> Result<(), Box<dyn Error>> {
-    let analyzer = ConstructorAnalyzer::new()?;
-
-    match analyzer.analyze_path(&config.input_path, config.all_functions) {
-        Ok(results) => {
-            if config.json_output {
-                let json_results: Vec<AnalysisResultJson> =
-                    results.iter().map(|r| r.into()).collect();
-                println!("{}", serde_json::to_string_pretty(&json_results)?);
-            } else if config.summary_only {
-                ResultPrinter::print_summary(&results);
-            } else {
-                ResultPrinter::print_results(&results);
-                if !results.is_empty() {
+///
+/// Returns the process exit code: [`EXIT_CLEAN`] or [`EXIT_FINDINGS`] depending on
+/// the failure policy, or [`EXIT_ERROR`] when some files could not be analyzed.
+/// Other errors also map to [`EXIT_ERROR`].
+pub fn run(config: CliConfig) -> Result<i32, Box<dyn Error>> {
+    let analyzer = ConstructorAnalyzer::with_options(config.analyzer_options.clone())?;
+    let baseline = match &config.baseline_path {
+        Some(path) => Some(Baseline::load(Path::new(path))?),
+        None => None,
+    };
+
+    if config.format == OutputFormat::Ndjson {
+        return stream_ndjson(&analyzer, &config, baseline.as_ref());
+    }
+
+    match analyzer.analyze_tree(&config.input_path, config.all_functions) {
+        Ok(PathAnalysis {
+            mut results,
+            errors,
+        }) => {
+            let comparison =
+                baseline
+                    .as_ref()
+                    .zip(config.baseline_path.as_deref())
+                    .map(|(baseline, path)| {
+                        let mut matcher = baseline.matcher();
+                        matcher.remove_known(&mut results);
+                        matcher.finish(path)
+                    });
+            config.filter.apply(&mut results);
+
+            for error in &errors {
+                eprintln!(
+                    "{}: could not analyze {}: {}",
+                    "Warning".yellow().bold(),
+                    error.path,
+                    error.message
+                );
+            }
+
+            match config.format {
+                OutputFormat::Text if config.summary_only => {
                     ResultPrinter::print_summary(&results);
+                    if let Some(comparison) = &comparison {
+                        ResultPrinter::print_baseline(comparison);
+                    }
+                }
+                OutputFormat::Text => {
+                    ResultPrinter::print_results(&results, config.show_suppressed);
+                    if !results.is_empty() {
+                        ResultPrinter::print_summary(&results);
+                    }
+                    if let Some(comparison) = &comparison {
+                        ResultPrinter::print_baseline(comparison);
+                    }
+                    println!("Analysis complete!");
+                }
+                format => {
+                    let context = ReportContext {
+                        root: config.input_path.clone(),
+                        options: config.report_options(),
+                        errors: errors.clone(),
+                        show_suppressed: config.show_suppressed,
+                        baseline: comparison,
+                    };
+                    let report = output::render(format, &results, &context)?;
+                    match &config.output_path {
+                        Some(path) => fs::write(path, report)?,
+                        None => print!("{report}"),
+                    }
                 }
-                println!("Analysis complete!");
             }
-            Ok(())
+
+            if errors.is_empty() {
+                Ok(config.fail_policy.exit_code(&results))
+            } else {
+                Ok(EXIT_ERROR)
+            }
         }
         Err(e) => {
             eprintln!("{}: {}", "Error".red().bold(), e);
@@ -43,3 +122,126 @@ pub fn run(config: CliConfig) -> Result<(), Box<dyn Error>> {
         }
     }
 }
+
+/// Writes a commented default configuration file into `directory`
+pub fn init(directory: &str, force: bool) -> Result<i32, Box<dyn Error>> {
+    let path = config::write_default(Path::new(directory), force)?;
+    println!("Wrote {}", path.display());
+    Ok(EXIT_CLEAN)
+}
+
+/// Records the current findings in a baseline file
+///
+/// Later runs given the file with `--baseline` only report findings it does not record.
+pub fn create_baseline(config: CliConfig) -> Result<i32, Box<dyn Error>> {
+    let analyzer = ConstructorAnalyzer::with_options(config.analyzer_options.clone())?;
+    let PathAnalysis {
+        mut results,
+        errors,
+    } = analyzer.analyze_tree(&config.input_path, config.all_functions)?;
+    config.filter.apply(&mut results);
+
+    for error in &errors {
+        eprintln!(
+            "{}: could not analyze {}: {}",
+            "Warning".yellow().bold(),
+            error.path,
+            error.message
+        );
+    }
+
+    let path = config
+        .baseline_path
+        .as_deref()
+        .unwrap_or(BASELINE_FILE_NAME);
+    let baseline = Baseline::from_results(&results);
+    baseline.save(Path::new(path))?;
+    println!("Recorded {} finding(s) in {path}", baseline.entries.len());
+
+    if errors.is_empty() {
+        Ok(EXIT_CLEAN)
+    } else {
+        Ok(EXIT_ERROR)
+    }
+}
+
+/// Analyzes the input and writes each file's results as NDJSON as soon as it is analyzed
+fn stream_ndjson(
+    analyzer: &ConstructorAnalyzer,
+    config: &CliConfig,
+    baseline: Option<&Baseline>,
+) -> Result<i32, Box<dyn Error>> {
+    if !Path::new(&config.input_path).exists() {
+        let e = ConstructorAnalyzerError::NotFound(config.input_path.clone());
+        eprintln!("{}: {}", "Error".red().bold(), e);
+        return Err(Box::new(e));
+    }
+
+    let output: Box<dyn Write> = match &config.output_path {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(io::stdout().lock()),
+    };
+    let context = ReportContext {
+        root: config.input_path.clone(),
+        options: config.report_options(),
+        errors: Vec::new(),
+        show_suppressed: config.show_suppressed,
+        baseline: None,
+    };
+    let mut writer = NdjsonWriter::new(output, &context)?;
+    let mut matcher = baseline.map(Baseline::matcher);
+
+    let mut counted_findings = 0;
+    let mut has_errors = false;
+    let mut write_error = None;
+    analyzer.analyze_path_streaming(
+        &config.input_path,
+        config.all_functions,
+        |file, outcome| {
+            let written = match outcome {
+                Ok(mut results) => {
+                    if let Some(matcher) = &mut matcher {
+                        matcher.remove_known(&mut results);
+                    }
+                    config.filter.apply(&mut results);
+                    counted_findings += config.fail_policy.counted_findings(&results);
+                    writer.write_results(&results)
+                }
+                Err(err) => {
+                    has_errors = true;
+                    let error = FileError {
+                        path: file.to_string_lossy().to_string(),
+                        message: err.to_string(),
+                    };
+                    eprintln!(
+                        "{}: could not analyze {}: {}",
+                        "Warning".yellow().bold(),
+                        error.path,
+                        error.message
+                    );
+                    writer.write_error(&error)
+                }
+            };
+            match written {
+                Ok(()) => ControlFlow::Continue(()),
+                Err(e) => {
+                    write_error = Some(e);
+                    ControlFlow::Break(())
+                }
+            }
+        },
+    )?;
+    if let Some(e) = write_error {
+        return Err(Box::new(e));
+    }
+    if let Some((matcher, path)) = matcher.zip(config.baseline_path.as_deref()) {
+        writer.write_baseline(&matcher.finish(path))?;
+    }
+    writer.finish()?;
+
+    if has_errors {
+        Ok(EXIT_ERROR)
+    } else {
+        Ok(config.fail_policy.exit_code_for_count(counted_findings))
+    }
+}
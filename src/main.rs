@@ -1,18 +1,27 @@
 use std::process;
 
-use construstor::{CliConfig, run};
+use construstor::{CliCommand, EXIT_ERROR, create_baseline, init, run};
 
 fn main() {
-    let config = match CliConfig::from_args() {
-        Ok(config) => config,
+    let command = match CliCommand::from_args() {
+        Ok(command) => command,
         Err(e) => {
             eprintln!("Error parsing arguments: {e}");
-            process::exit(1);
+            process::exit(EXIT_ERROR);
         }
     };
 
-    if let Err(e) = run(config) {
-        eprintln!("Fatal error: {e}");
-        process::exit(1);
+    let outcome = match command {
+        CliCommand::Analyze(config) => run(*config),
+        CliCommand::Init { directory, force } => init(&directory, force),
+        CliCommand::CreateBaseline(config) => create_baseline(*config),
+    };
+
+    match outcome {
+        Ok(code) => process::exit(code),
+        Err(e) => {
+            eprintln!("Fatal error: {e}");
+            process::exit(EXIT_ERROR);
+        }
     }
 }
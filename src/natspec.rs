@@ -0,0 +1,134 @@
+//! NatSpec annotations that document address parameters which may be zero on purpose
+//!
+//! A parameter is intentionally unchecked when the doc comment of its function lists it
+//! in a `@custom:zero-address-allowed` tag, or when its `@param` description says it may
+//! be `address(0)`.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+/// The tag followed by nothing but a comma- or space-separated list of names on its line
+static ALLOWED_TAG_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?m)@custom:zero-address-allowed[ \t]+([\w$]+(?:[ \t]*,[ \t]*[\w$]+|[ \t]+[\w$]+)*)[ \t,]*(?:\*/)?[ \t]*\r?$",
+    )
+    .expect("NatSpec tag regex is valid")
+});
+
+static PARAM_TAG_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"@param\s+(\w+)([^@]*)").expect("NatSpec tag regex is valid"));
+
+static MAY_BE_ZERO_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:may|can)\s+be\s+(?:address\(0\)|the\s+zero\s+address|zero)")
+        .expect("NatSpec description regex is valid")
+});
+
+/// Returns the NatSpec comment directly preceding `offset` in `source`, or an empty string
+///
+/// Both a run of `///` lines and a `/** ... */` block are recognized.
+pub fn doc_comment_before(source: &str, offset: usize) -> &str {
+    let before = source[..offset].trim_end();
+
+    if before.ends_with("*/") {
+        return match before.rfind("/*") {
+            Some(start) if before[start..].starts_with("/**") => &before[start..],
+            _ => "",
+        };
+    }
+
+    let mut doc_start = before.len();
+    let mut end = before.len();
+    loop {
+        let line_start = before[..end].rfind('\n').map_or(0, |i| i + 1);
+        if !before[line_start..end].trim_start().starts_with("///") {
+            break;
+        }
+        doc_start = line_start;
+        if line_start == 0 {
+            break;
+        }
+        end = line_start - 1;
+    }
+    before[doc_start..].trim_start()
+}
+
+/// Lists the names a doc comment marks as allowed to be `address(0)`
+///
+/// Names are returned as written; callers keep those that match a parameter.
+pub fn zero_address_allowed(doc: &str) -> Vec<String> {
+    let mut names: Vec<String> = ALLOWED_TAG_REGEX
+        .captures_iter(doc)
+        .flat_map(|captures| {
+            captures[1]
+                .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+                .filter(|name| !name.is_empty())
+                .map(String::from)
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    names.extend(
+        PARAM_TAG_REGEX
+            .captures_iter(doc)
+            .filter(|captures| MAY_BE_ZERO_REGEX.is_match(&captures[2]))
+            .map(|captures| captures[1].to_string()),
+    );
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_doc_comment_before_finds_adjacent_comment() {
+        let source = "\
+/** @notice Other */
+function a() {}
+/// @notice Sets the referrer
+/// @param _referrer may be address(0)
+function setReferrer(address _referrer) {}
+/* plain */
+function b() {}
+";
+        let offset = |name: &str| source.find(name).unwrap();
+
+        assert_eq!(
+            doc_comment_before(source, offset("function setReferrer")),
+            "/// @notice Sets the referrer\n/// @param _referrer may be address(0)"
+        );
+        assert_eq!(
+            doc_comment_before(source, offset("function a")),
+            "/** @notice Other */"
+        );
+        assert_eq!(doc_comment_before(source, offset("function b")), "");
+    }
+
+    #[test]
+    fn test_zero_address_allowed_reads_custom_tag_and_param_descriptions() {
+        let doc = "\
+/**
+ * @custom:zero-address-allowed _hook, _fallback
+ * @param _owner The owner, never zero
+ * @param _referrer Optional referrer; can be the zero address
+ * @param _fee May be zero to disable fees
+ */";
+
+        assert_eq!(
+            zero_address_allowed(doc),
+            vec!["_hook", "_fallback", "_referrer", "_fee"]
+        );
+
+        // Prose after the tag is not part of the list
+        let doc = "\
+/// @custom:zero-address-allowed _hook _fallback
+/// Unlike _owner, which must always be set.
+/// @custom:zero-address-allowed _router (optional)
+/** @custom:zero-address-allowed _vault */";
+        assert_eq!(
+            zero_address_allowed(doc),
+            vec!["_hook", "_fallback", "_vault"]
+        );
+    }
+}
@@ -0,0 +1,54 @@
+//! Compiler-style one-line findings for editor quickfix lists and grep
+
+use std::fmt::Write;
+
+use super::report_path;
+use crate::types::AnalysisResult;
+
+/// Writes one `path:line:col: warning[zero-address]: message [fingerprint]` line per missing
+/// validation
+pub fn to_compact(results: &[AnalysisResult]) -> String {
+    let mut lines = String::new();
+
+    for result in results {
+        let path = report_path(&result.file_path);
+        for finding in &result.findings {
+            let message = result.finding_message(finding);
+            let mut chars = message.chars();
+            let message = match chars.next() {
+                Some(first) => first.to_lowercase().chain(chars).collect(),
+                None => message,
+            };
+
+            let _ = writeln!(
+                lines,
+                "{path}:{}:{}: warning[zero-address]: {message} [{}]",
+                finding.line, finding.column, finding.fingerprint
+            );
+        }
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConstructorAnalyzer;
+
+    #[test]
+    fn test_compact_line_per_finding() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let results = analyzer.analyze_source(
+            "contract Vault {\n    address owner;\n    constructor(address _owner, address _guardian) {\n        owner = _owner;\n    }\n}\n",
+            "src/Vault.sol",
+            false,
+        );
+
+        assert_eq!(
+            to_compact(&results),
+            "src/Vault.sol:3:25: warning[zero-address]: parameter '_owner' of constructor in Vault is not checked against address(0) [9ac34327e3075fb6]\n\
+             src/Vault.sol:3:41: warning[zero-address]: parameter '_guardian' of constructor in Vault is not checked against address(0) [777d8b942713f172]\n"
+        );
+    }
+}
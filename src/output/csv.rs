@@ -0,0 +1,168 @@
+//! CSV export with one row per address parameter, for spreadsheet triage
+
+use std::fmt::Write;
+
+use crate::types::{AnalysisResult, ValidationType};
+
+/// Column headers, in their stable order
+pub const COLUMNS: [&str; 10] = [
+    "file",
+    "contract",
+    "function",
+    "function_type",
+    "parameter",
+    "type",
+    "status",
+    "validation_kind",
+    "line",
+    "fingerprint",
+];
+
+/// Builds a CSV document with one row per address parameter of every analyzed function
+///
+/// `status` is `validated` when a `require()`, a validator call or an `== address(0)` guard
+/// checks the parameter, `conditional` when it is only compared with `!= address(0)` (the function
+/// branches on it instead of rejecting it), `allowed` when its NatSpec says it may be
+/// `address(0)`, `suppressed` when a missing check is accepted by a suppression comment,
+/// `baseline` when it is accepted by the baseline and `missing` otherwise. `line` is the
+/// line of the check, or of the parameter declaration when it is missing, and `fingerprint`
+/// identifies the finding of a missing or suppressed check.
+pub fn to_csv(results: &[AnalysisResult]) -> String {
+    let mut csv = COLUMNS.join(",");
+    csv.push('\n');
+
+    for result in results {
+        for (parameter_type, parameter) in &result.address_arguments {
+            let evidence: Vec<_> = result
+                .validation_evidence
+                .iter()
+                .filter(|evidence| evidence.variable == *parameter)
+                .collect();
+            let guard = evidence.iter().find(|evidence| {
+                evidence.kind != ValidationType::EqualityCheck || !evidence.source.contains("!=")
+            });
+
+            let suppressed = result
+                .suppressed_findings
+                .iter()
+                .find(|finding| finding.parameter == *parameter);
+            let finding = suppressed.or_else(|| {
+                result
+                    .findings
+                    .iter()
+                    .find(|finding| finding.parameter == *parameter)
+            });
+
+            let (status, kind, line) = match (guard, evidence.first()) {
+                (Some(guard), _) => ("validated", Some(&guard.kind), guard.line),
+                (None, Some(check)) => ("conditional", Some(&check.kind), check.line),
+                (None, None) => {
+                    let status = if result.validated_variables.contains(parameter) {
+                        "validated"
+                    } else if result.zero_address_allowed.contains(parameter) {
+                        "allowed"
+                    } else if suppressed.is_some() {
+                        "suppressed"
+                    } else if result.missing_validations.contains(parameter) {
+                        "missing"
+                    } else {
+                        // The finding was left out because the baseline records it
+                        "baseline"
+                    };
+                    (
+                        status,
+                        None,
+                        finding.map_or(result.line, |finding| finding.line),
+                    )
+                }
+            };
+
+            let row = [
+                result.file_path.as_str(),
+                result.contract_name.as_deref().unwrap_or(""),
+                result.function_type.name(),
+                result.function_type.kind(),
+                parameter,
+                parameter_type,
+                status,
+                match kind {
+                    Some(ValidationType::RequireStatement) => "require",
+                    Some(ValidationType::EqualityCheck) => "equality",
+                    Some(ValidationType::ValidatorCall) => "validator",
+                    None => "",
+                },
+                &line.to_string(),
+                finding.map_or("", |finding| finding.fingerprint.as_str()),
+            ];
+            let fields: Vec<String> = row.iter().map(|field| escape_field(field)).collect();
+            let _ = writeln!(csv, "{}", fields.join(","));
+        }
+    }
+
+    csv
+}
+
+/// Quotes a field when it contains a separator, quote or line break
+fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConstructorAnalyzer;
+    use crate::baseline::Baseline;
+
+    #[test]
+    fn test_csv_has_one_row_per_parameter() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let results = analyzer.analyze_source(
+            "contract Vault {\n    constructor(address _a, address[] memory _b, address _c) {\n        require(_a != address(0));\n        if (_c != address(0)) { c = _c; }\n    }\n}\n",
+            "Vault.sol",
+            false,
+        );
+
+        let csv = to_csv(&results);
+
+        assert_eq!(
+            csv,
+            "file,contract,function,function_type,parameter,type,status,validation_kind,line,fingerprint\n\
+             Vault.sol,Vault,constructor,constructor,_a,address,validated,require,3,\n\
+             Vault.sol,Vault,constructor,constructor,_b,address[] memory,missing,,2,23d5856dbbf6ce22\n\
+             Vault.sol,Vault,constructor,constructor,_c,address,conditional,equality,4,\n"
+        );
+    }
+
+    #[test]
+    fn test_csv_marks_baselined_parameters() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let source = "contract Vault {\n    constructor(address _a) { a = _a; }\n}\n";
+        let baseline = Baseline::from_results(&analyzer.analyze_source(source, "Vault.sol", false));
+
+        let mut results = analyzer.analyze_source(
+            "contract Vault {\n    constructor(address _a) { a = _a; }\n    function initialize(address _b) external { b = _b; }\n}\n",
+            "Vault.sol",
+            false,
+        );
+        let mut matcher = baseline.matcher();
+        matcher.remove_known(&mut results);
+        let csv = to_csv(&results);
+
+        let statuses: Vec<&str> = csv
+            .lines()
+            .skip(1)
+            .map(|row| row.split(',').nth(6).unwrap())
+            .collect();
+        assert_eq!(statuses, vec!["baseline", "missing"]);
+    }
+
+    #[test]
+    fn test_escape_field() {
+        assert_eq!(escape_field("plain"), "plain");
+        assert_eq!(escape_field("a, \"b\""), "\"a, \"\"b\"\"\"");
+    }
+}
@@ -0,0 +1,120 @@
+//! GitHub Actions workflow commands that annotate pull request diffs
+
+use std::fmt::Write;
+
+use super::report_path;
+use crate::severity::Severity;
+use crate::types::AnalysisResult;
+
+/// Writes one `::error`, `::warning` or `::notice` command per missing validation
+///
+/// Critical and high findings become errors, medium ones warnings and the rest notices,
+/// mirroring the SARIF levels.
+pub fn to_github(results: &[AnalysisResult]) -> String {
+    let mut commands = String::new();
+
+    for result in results {
+        let file = report_path(&result.file_path);
+
+        for finding in &result.findings {
+            let title = format!(
+                "Missing zero-address check [{}: {}]",
+                finding.severity, finding.rule
+            );
+            let usage: Vec<&str> = finding
+                .usage
+                .iter()
+                .map(|usage| usage.description())
+                .collect();
+            let message = format!(
+                "{} ({}). Fingerprint: {}",
+                result.finding_message(finding),
+                usage.join(", "),
+                finding.fingerprint
+            );
+
+            let _ = writeln!(
+                commands,
+                "::{} file={},line={},col={},title={}::{}",
+                command(finding.severity),
+                escape_property(&file),
+                finding.line,
+                finding.column,
+                escape_property(&title),
+                escape_data(&message)
+            );
+        }
+    }
+
+    commands
+}
+
+/// Maps a severity to the workflow command that annotates it
+fn command(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical | Severity::High => "error",
+        Severity::Medium => "warning",
+        Severity::Low | Severity::Info => "notice",
+    }
+}
+
+/// Escapes a command message
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a command property value, which additionally cannot contain `:` or `,`
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConstructorAnalyzer;
+
+    #[test]
+    fn test_github_annotates_each_finding() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let results = analyzer.analyze_source(
+            "contract Vault {\n    address owner;\n    constructor(address _owner) {\n        owner = _owner;\n    }\n}\n",
+            "./src/Vault.sol",
+            false,
+        );
+
+        assert_eq!(
+            to_github(&results),
+            format!(
+                "::error file=src/Vault.sol,line=3,col=25,title=Missing zero-address check [high%3A initializer-state]::\
+                 Parameter '_owner' of constructor in Vault is not checked against address(0) (stored to state). \
+                 Fingerprint: {}\n",
+                results[0].findings[0].fingerprint
+            )
+        );
+    }
+
+    #[test]
+    fn test_github_escapes_properties_and_data() {
+        assert_eq!(escape_data("50%\r\nnext: a, b"), "50%25%0D%0Anext: a, b");
+        assert_eq!(
+            escape_property("50%\r\nnext: a, b"),
+            "50%25%0D%0Anext%3A a%2C b"
+        );
+
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let results = analyzer.analyze_source(
+            "contract Vault {\n    address owner;\n    constructor(address _owner) {\n        owner = _owner;\n    }\n}\n",
+            "/work/src/a,b:c.sol",
+            false,
+        );
+
+        assert!(to_github(&results).starts_with("::error file=/work/src/a%2Cb%3Ac.sol,line=3,"));
+    }
+
+    #[test]
+    fn test_github_without_findings_writes_nothing() {
+        assert_eq!(to_github(&[]), "");
+    }
+}
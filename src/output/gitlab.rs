@@ -0,0 +1,82 @@
+//! GitLab Code Quality report for merge request widgets
+
+use serde_json::{Value, json};
+
+use super::report_path;
+use crate::severity::Severity;
+use crate::types::AnalysisResult;
+
+/// Builds the Code Quality issue array with one issue per missing validation
+///
+/// Fingerprints do not depend on line numbers, so GitLab can tell new issues from
+/// resolved ones when code moves around.
+pub fn to_gitlab(results: &[AnalysisResult]) -> Value {
+    let issues: Vec<Value> = results
+        .iter()
+        .flat_map(|result| {
+            result.findings.iter().map(move |finding| {
+                json!({
+                    "type": "issue",
+                    "description": result.finding_message(finding),
+                    "check_name": format!("zero-address/{}", finding.rule),
+                    "fingerprint": finding.fingerprint,
+                    "severity": severity(finding.severity),
+                    "categories": ["Security"],
+                    "location": {
+                        "path": report_path(&result.file_path),
+                        "lines": { "begin": finding.line },
+                    },
+                })
+            })
+        })
+        .collect();
+
+    Value::Array(issues)
+}
+
+/// Maps a severity to a Code Quality severity
+fn severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "blocker",
+        Severity::High => "critical",
+        Severity::Medium => "major",
+        Severity::Low => "minor",
+        Severity::Info => "info",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConstructorAnalyzer;
+
+    #[test]
+    fn test_gitlab_issue_fields() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let source = "contract Vault {\n    address owner;\n    constructor(address _owner) {\n        owner = _owner;\n    }\n}\n";
+        let results = analyzer.analyze_source(source, "./src/Vault.sol", false);
+        let issue = &to_gitlab(&results)[0];
+
+        assert_eq!(issue["check_name"], "zero-address/initializer-state");
+        assert_eq!(issue["severity"], "critical");
+        assert_eq!(issue["location"]["path"], "src/Vault.sol");
+        assert_eq!(issue["location"]["lines"]["begin"], 3);
+
+        // Shifting the code down keeps the fingerprint
+        let shifted = analyzer.analyze_source(&format!("\n\n{source}"), "./src/Vault.sol", false);
+        assert_eq!(issue["fingerprint"], to_gitlab(&shifted)[0]["fingerprint"]);
+    }
+
+    #[test]
+    fn test_gitlab_keeps_absolute_paths_and_empty_reports() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let source = "contract Vault {\n    address owner;\n    constructor(address _owner) {\n        owner = _owner;\n    }\n}\n";
+        let results = analyzer.analyze_source(source, "/work/src/Vault.sol", false);
+
+        assert_eq!(
+            to_gitlab(&results)[0]["location"]["path"],
+            "/work/src/Vault.sol"
+        );
+        assert_eq!(to_gitlab(&[]), json!([]));
+    }
+}
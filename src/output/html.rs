@@ -0,0 +1,445 @@
+//! Self-contained HTML report for audit hand-offs
+
+use std::fmt::Write;
+use std::path::Path;
+
+use crate::output::{escape_xml, group_by};
+use crate::severity::Severity;
+use crate::types::{AnalysisResult, Summary, ValidationCounts};
+
+/// Solidity keywords highlighted in excerpts
+const KEYWORDS: &[&str] = &[
+    "assembly",
+    "calldata",
+    "catch",
+    "constant",
+    "constructor",
+    "contract",
+    "delete",
+    "do",
+    "else",
+    "emit",
+    "event",
+    "external",
+    "false",
+    "for",
+    "function",
+    "if",
+    "immutable",
+    "interface",
+    "internal",
+    "library",
+    "memory",
+    "modifier",
+    "new",
+    "override",
+    "payable",
+    "private",
+    "public",
+    "pure",
+    "require",
+    "return",
+    "returns",
+    "revert",
+    "storage",
+    "true",
+    "try",
+    "unchecked",
+    "view",
+    "virtual",
+    "while",
+];
+
+/// Elementary type names highlighted in excerpts, matched by prefix for sized types
+const TYPES: &[&str] = &[
+    "address", "bool", "bytes", "int", "mapping", "string", "uint",
+];
+
+/// Inline stylesheet, so the report has no external assets
+const STYLE: &str = r#"
+body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; margin: 2rem; color: #1f2328; }
+h1 { font-size: 1.6rem; }
+h2 { font-size: 1.25rem; margin-top: 2rem; border-bottom: 1px solid #d0d7de; padding-bottom: .3rem; }
+table { border-collapse: collapse; margin: 1rem 0; font-size: .9rem; }
+th, td { border: 1px solid #d0d7de; padding: .35rem .6rem; text-align: left; }
+th { background: #f6f8fa; }
+#findings th { cursor: pointer; user-select: none; }
+#findings th:after { content: " \2195"; color: #8c959f; }
+td.num { text-align: right; }
+tr.suppressed { color: #8c959f; }
+.sev { font-weight: 600; text-transform: uppercase; font-size: .8rem; }
+.sev-critical { color: #82071e; } .sev-high { color: #cf222e; } .sev-medium { color: #9a6700; }
+.sev-low { color: #0969da; } .sev-info { color: #57606a; }
+.controls { margin: 1rem 0; display: flex; gap: .5rem; }
+.controls input { width: 20rem; }
+pre { background: #f6f8fa; padding: 1rem; overflow-x: auto; border-radius: 6px; }
+.kw { color: #cf222e; } .ty { color: #8250df; } .str { color: #0a3069; } .num-lit { color: #0550ae; }
+.com { color: #6e7781; font-style: italic; }
+mark { background: #ffd8b5; border-radius: 3px; padding: 0 2px; }
+"#;
+
+/// Inline script that sorts and filters the findings table
+const SCRIPT: &str = r#"
+(function () {
+  const table = document.getElementById("findings");
+  if (!table) return;
+  const body = table.tBodies[0];
+  const search = document.getElementById("search");
+  const severity = document.getElementById("severity");
+  table.querySelectorAll("th").forEach(function (th, column) {
+    let ascending = true;
+    th.addEventListener("click", function () {
+      const rows = Array.from(body.rows);
+      rows.sort(function (a, b) {
+        const x = a.cells[column].dataset.sort || a.cells[column].textContent;
+        const y = b.cells[column].dataset.sort || b.cells[column].textContent;
+        const order = isNaN(x) || isNaN(y) ? x.localeCompare(y) : x - y;
+        return ascending ? order : -order;
+      });
+      ascending = !ascending;
+      rows.forEach(function (row) { body.appendChild(row); });
+    });
+  });
+  function filter() {
+    const text = search.value.toLowerCase();
+    const minimum = Number(severity.value);
+    Array.from(body.rows).forEach(function (row) {
+      const matches = row.textContent.toLowerCase().includes(text)
+        && Number(row.dataset.rank) >= minimum;
+      row.style.display = matches ? "" : "none";
+    });
+  }
+  search.addEventListener("input", filter);
+  severity.addEventListener("change", filter);
+})();
+"#;
+
+/// Builds a single-file HTML report with summary, rollups, findings table and excerpts
+///
+/// Findings suppressed by comments are counted in the summary and, with `show_suppressed`,
+/// listed in the findings table.
+pub fn to_html(results: &[AnalysisResult], show_suppressed: bool) -> String {
+    let summary = Summary::from_results(results);
+    let mut html = String::new();
+
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Construstor report</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+         <h1>Construstor zero address report</h1>\n<p>Generated by {} {}.</p>\n",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION")
+    );
+
+    // Summary, with the same numbers as the terminal summary
+    html.push_str("<h2>Summary</h2>\n<table>\n<tr><th></th><th>Functions</th><th>Fully validated</th><th>Partially validated</th><th>Not validated</th></tr>\n");
+    let mut row = |label: &str, counts: &ValidationCounts| {
+        let _ = writeln!(
+            html,
+            "<tr><th>{}</th><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+            label,
+            counts.with_address_arguments,
+            counts.fully_validated,
+            counts.partially_validated,
+            counts.not_validated
+        );
+    };
+    row("With address arguments", &summary.overall);
+    row("Permissionless", &summary.permissionless);
+    row("Trusted caller", &summary.trusted_caller);
+    if summary.by_origin.len() > 1 {
+        for (origin, counts) in &summary.by_origin {
+            row(&format!("Origin: {}", origin.label()), counts);
+        }
+    }
+    let _ = write!(
+        html,
+        "</table>\n<p>{} function(s) analyzed",
+        summary.total_functions
+    );
+    if summary.suppressed_findings > 0 {
+        let _ = write!(
+            html,
+            ", {} finding(s) suppressed by comment",
+            summary.suppressed_findings
+        );
+    }
+    html.push_str(".</p>\n");
+
+    write_rollups(&mut html, results);
+    write_findings_table(&mut html, results, show_suppressed);
+    write_excerpts(&mut html, results, show_suppressed);
+
+    let _ = write!(html, "<script>{SCRIPT}</script>\n</body>\n</html>\n");
+    html
+}
+
+/// Writes per-directory finding counts
+fn write_rollups(html: &mut String, results: &[AnalysisResult]) {
+    html.push_str(
+        "<h2>By directory</h2>\n<table>\n<tr><th>Directory</th><th>Functions</th><th>Findings</th>",
+    );
+    for severity in Severity::NAMES.iter().rev() {
+        let _ = write!(html, "<th>{severity}</th>");
+    }
+    html.push_str("</tr>\n");
+
+    let directories = group_by(results, |result| {
+        Path::new(&result.file_path)
+            .parent()
+            .map(|parent| parent.to_string_lossy().to_string())
+            .filter(|parent| !parent.is_empty())
+            .unwrap_or_else(|| ".".to_string())
+    });
+    for (directory, directory_results) in directories {
+        let findings: Vec<_> = directory_results
+            .iter()
+            .flat_map(|result| &result.findings)
+            .collect();
+        let _ = write!(
+            html,
+            "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td>",
+            escape_xml(&directory),
+            directory_results.len(),
+            findings.len()
+        );
+        for name in Severity::NAMES.iter().rev() {
+            let count = findings
+                .iter()
+                .filter(|finding| finding.severity.as_str() == *name)
+                .count();
+            let _ = write!(html, "<td class=\"num\">{count}</td>");
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n");
+}
+
+/// Writes the sortable, filterable table of findings, and of suppressed findings with
+/// `show_suppressed`
+fn write_findings_table(html: &mut String, results: &[AnalysisResult], show_suppressed: bool) {
+    html.push_str(
+        "<h2>Findings</h2>\n<div class=\"controls\">\
+        <input id=\"search\" type=\"search\" placeholder=\"Filter findings\">\
+        <select id=\"severity\"><option value=\"0\">All severities</option>",
+    );
+    for (rank, name) in Severity::NAMES.iter().enumerate().skip(1) {
+        let _ = write!(html, "<option value=\"{rank}\">{name} and above</option>");
+    }
+    html.push_str("</select></div>\n<table id=\"findings\">\n<thead><tr><th>File</th><th>Contract</th><th>Function</th><th>Parameter</th><th>Severity</th><th>Rule</th><th>Usage</th><th>Line</th><th>Fingerprint</th></tr></thead>\n<tbody>\n");
+
+    for (index, result) in results.iter().enumerate() {
+        let suppressed = if show_suppressed {
+            result.suppressed_findings.as_slice()
+        } else {
+            &[]
+        };
+        let findings = result
+            .findings
+            .iter()
+            .map(|finding| (finding, false))
+            .chain(suppressed.iter().map(|finding| (finding, true)));
+        for (finding, suppressed) in findings {
+            let rank = finding.severity as usize;
+            let usage: Vec<&str> = finding
+                .usage
+                .iter()
+                .map(|usage| usage.description())
+                .collect();
+            let _ = writeln!(
+                html,
+                "<tr data-rank=\"{rank}\"{}><td>{}</td><td>{}</td><td><a href=\"#fn-{index}\">{}</a></td>\
+                 <td><code>{}</code>{}</td><td data-sort=\"{rank}\" class=\"sev sev-{severity}\">{severity}</td>\
+                 <td>{}</td><td>{}</td><td class=\"num\">{}</td><td><code>{}</code></td></tr>",
+                if suppressed {
+                    " class=\"suppressed\""
+                } else {
+                    ""
+                },
+                escape_xml(&result.file_path),
+                escape_xml(result.contract_name.as_deref().unwrap_or("")),
+                escape_xml(&result.function_type.to_string()),
+                escape_xml(&finding.parameter),
+                if suppressed { " (suppressed)" } else { "" },
+                finding.rule,
+                usage.join(", "),
+                finding.line,
+                finding.fingerprint,
+                severity = finding.severity,
+            );
+        }
+    }
+    html.push_str("</tbody>\n</table>\n");
+}
+
+/// Writes highlighted excerpts of the functions listed in the findings table
+fn write_excerpts(html: &mut String, results: &[AnalysisResult], show_suppressed: bool) {
+    html.push_str("<h2>Function excerpts</h2>\n");
+    for (index, result) in results.iter().enumerate() {
+        let listed = !result.findings.is_empty()
+            || show_suppressed && !result.suppressed_findings.is_empty();
+        if !listed {
+            continue;
+        }
+        let unvalidated: Vec<&str> = result
+            .findings
+            .iter()
+            .map(|finding| finding.parameter.as_str())
+            .collect();
+        let _ = writeln!(
+            html,
+            "<section id=\"fn-{index}\">\n<h3>{}</h3>\n<p>{}:{}{}</p>\n<pre><code>{}</code></pre>\n</section>",
+            escape_xml(&result.qualified_name()),
+            escape_xml(&result.file_path),
+            result.line,
+            if result.trusted_caller {
+                format!(
+                    " &middot; trusted caller only: {}",
+                    escape_xml(&result.access_controls.join(", "))
+                )
+            } else {
+                String::new()
+            },
+            highlight_solidity(&result.code, &unvalidated)
+        );
+    }
+}
+
+/// Escapes Solidity source for HTML, highlighting syntax and the given identifiers
+fn highlight_solidity(code: &str, marked: &[&str]) -> String {
+    let mut html = String::with_capacity(code.len() * 2);
+    let mut rest = code;
+
+    let span = |html: &mut String, class: &str, text: &str| {
+        let _ = write!(html, "<span class=\"{class}\">{}</span>", escape_xml(text));
+    };
+
+    while let Some(c) = rest.chars().next() {
+        let token_len = if rest.starts_with("//") {
+            let len = rest.find('\n').unwrap_or(rest.len());
+            span(&mut html, "com", &rest[..len]);
+            len
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            let len = comment.find("*/").map_or(rest.len(), |end| end + 4);
+            span(&mut html, "com", &rest[..len]);
+            len
+        } else if c == '"' || c == '\'' {
+            let mut len = 1;
+            let mut escaped = false;
+            for next in rest[1..].chars() {
+                len += next.len_utf8();
+                if next == c && !escaped {
+                    break;
+                }
+                escaped = next == '\\' && !escaped;
+            }
+            span(&mut html, "str", &rest[..len]);
+            len
+        } else if c.is_ascii_digit() {
+            let len = rest
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .unwrap_or(rest.len());
+            span(&mut html, "num-lit", &rest[..len]);
+            len
+        } else if c.is_alphabetic() || c == '_' || c == '$' {
+            let len = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+                .unwrap_or(rest.len());
+            let word = &rest[..len];
+            if marked.contains(&word) {
+                let _ = write!(html, "<mark>{}</mark>", escape_xml(word));
+            } else if KEYWORDS.contains(&word) {
+                span(&mut html, "kw", word);
+            } else if TYPES.iter().any(|ty| {
+                word.strip_prefix(ty)
+                    .is_some_and(|size| size.chars().all(|c| c.is_ascii_digit()))
+            }) {
+                span(&mut html, "ty", word);
+            } else {
+                html.push_str(&escape_xml(word));
+            }
+            len
+        } else {
+            html.push_str(&escape_xml(&rest[..c.len_utf8()]));
+            c.len_utf8()
+        };
+        rest = &rest[token_len..];
+    }
+
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConstructorAnalyzer;
+    use crate::types::FileOrigin;
+
+    #[test]
+    fn test_highlight_solidity_marks_unvalidated_parameters() {
+        let html = highlight_solidity(
+            "function f(address _a) { // set <a>\n owner = _a; }",
+            &["_a"],
+        );
+
+        assert_eq!(
+            html,
+            "<span class=\"kw\">function</span> f(<span class=\"ty\">address</span> <mark>_a</mark>) { \
+             <span class=\"com\">// set &lt;a&gt;</span>\n owner = <mark>_a</mark>; }"
+        );
+    }
+
+    #[test]
+    fn test_html_report_is_self_contained() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let results = analyzer.analyze_source(
+            "contract Vault { constructor(address _owner) { owner = _owner; } }",
+            "Vault.sol",
+            false,
+        );
+
+        let html = to_html(&results, false);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(!html.contains("<link") && !html.contains("src=\""));
+        assert!(html.contains("<tr data-rank=\"3\"><td>Vault.sol</td><td>Vault</td>"));
+        assert!(html.contains("<section id=\"fn-0\">"));
+        assert!(!html.contains("Origin:"));
+    }
+
+    #[test]
+    fn test_html_counts_and_lists_suppressed_findings() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let results = analyzer.analyze_source(
+            "contract Vault {\n    // construstor-disable-next-line zero-address:_owner\n    \
+             constructor(address _owner) { owner = _owner; }\n}",
+            "Vault.sol",
+            false,
+        );
+
+        let hidden = to_html(&results, false);
+        assert!(hidden.contains("1 function(s) analyzed, 1 finding(s) suppressed by comment."));
+        assert!(!hidden.contains("<tr data-rank"));
+        assert!(!hidden.contains("<section id=\"fn-0\">"));
+
+        let shown = to_html(&results, true);
+        assert!(shown.contains("<tr data-rank=\"3\" class=\"suppressed\"><td>Vault.sol</td>"));
+        assert!(shown.contains("<td><code>_owner</code> (suppressed)</td>"));
+        assert!(shown.contains("<section id=\"fn-0\">"));
+    }
+
+    #[test]
+    fn test_html_summary_breaks_down_mixed_origins() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let source = "contract Vault { constructor(address _owner) { owner = _owner; } }";
+        let mut results = analyzer.analyze_source(source, "src/Vault.sol", false);
+        let mut dependency = analyzer.analyze_source(source, "lib/Vault.sol", false);
+        dependency[0].origin = FileOrigin::Dependency;
+        results.extend(dependency);
+
+        let html = to_html(&results, false);
+
+        assert!(html.contains("<tr><th>Origin: Dependency</th><td class=\"num\">1</td>"));
+        assert!(html.contains("<tr><th>Origin: First-party</th><td class=\"num\">1</td>"));
+    }
+}
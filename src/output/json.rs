@@ -0,0 +1,127 @@
+//! Versioned JSON report with run metadata, summary and per-file errors
+
+use std::time::SystemTime;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::ReportContext;
+use crate::baseline::BaselineComparison;
+use crate::types::{AnalysisResult, AnalysisResultJson, FileError, Summary};
+
+/// Version of the report layout, bumped on every breaking change
+pub const SCHEMA_VERSION: &str = "1.6.0";
+
+/// JSON Schema describing [`JsonReport`]
+pub const JSON_SCHEMA: &str = include_str!("../../schema/report.schema.json");
+
+/// Top-level object written by `--format json`
+#[derive(Debug, Serialize)]
+pub struct JsonReport<'a> {
+    /// Version of the report layout, see [`SCHEMA_VERSION`]
+    pub schema_version: &'static str,
+    /// Tool that produced the report
+    pub tool: Tool,
+    /// Path that was analyzed
+    pub root: &'a str,
+    /// When the report was generated, in RFC 3339 format
+    pub generated_at: String,
+    /// Options the run was configured with
+    pub options: &'a Value,
+    /// Same numbers as the terminal summary
+    pub summary: Summary,
+    /// Files that could not be analyzed
+    pub errors: &'a [FileError],
+    /// How the results compare with the baseline, when one was given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<&'a BaselineComparison>,
+    /// One entry per analyzed function
+    pub results: Vec<AnalysisResultJson>,
+}
+
+/// Name and version of the tool
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Tool {
+    pub name: &'static str,
+    pub version: &'static str,
+}
+
+impl Tool {
+    /// This build of the tool
+    pub const CURRENT: Tool = Tool {
+        name: env!("CARGO_PKG_NAME"),
+        version: env!("CARGO_PKG_VERSION"),
+    };
+}
+
+/// Wraps results in the versioned report envelope
+pub fn to_json<'a>(results: &[AnalysisResult], context: &'a ReportContext) -> JsonReport<'a> {
+    JsonReport {
+        schema_version: SCHEMA_VERSION,
+        tool: Tool::CURRENT,
+        root: &context.root,
+        generated_at: timestamp(),
+        options: &context.options,
+        summary: Summary::from_results(results),
+        errors: &context.errors,
+        baseline: context.baseline.as_ref(),
+        results: results.iter().map(|r| r.into()).collect(),
+    }
+}
+
+/// Returns the current time in RFC 3339 format, to the second
+pub(crate) fn timestamp() -> String {
+    humantime::format_rfc3339_seconds(SystemTime::now()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConstructorAnalyzer;
+
+    #[test]
+    fn test_report_matches_schema_shape() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let results = analyzer.analyze_source(
+            "contract Vault {\n    constructor(address _owner) {}\n}\n",
+            "Vault.sol",
+            false,
+        );
+        let context = ReportContext {
+            root: "contracts".to_string(),
+            options: serde_json::json!({ "all_functions": false }),
+            errors: vec![FileError {
+                path: "contracts/Broken.sol".to_string(),
+                message: "stream did not contain valid UTF-8".to_string(),
+            }],
+            ..ReportContext::default()
+        };
+
+        let report = serde_json::to_value(to_json(&results, &context)).unwrap();
+        let schema: Value = serde_json::from_str(JSON_SCHEMA).unwrap();
+
+        assert_eq!(report["schema_version"], SCHEMA_VERSION);
+        assert_eq!(
+            schema["properties"]["schema_version"]["const"],
+            SCHEMA_VERSION
+        );
+        assert_eq!(report["summary"]["not_validated"], 1);
+        assert_eq!(report["errors"][0]["path"], "contracts/Broken.sol");
+
+        // Every required property of the schema is present, and nothing undocumented
+        let properties = schema["properties"].as_object().unwrap();
+        for key in schema["required"].as_array().unwrap() {
+            assert!(report.get(key.as_str().unwrap()).is_some(), "missing {key}");
+        }
+        for key in report.as_object().unwrap().keys() {
+            assert!(properties.contains_key(key), "undocumented {key}");
+        }
+        let result_properties = schema["$defs"]["result"]["properties"].as_object().unwrap();
+        for key in report["results"][0].as_object().unwrap().keys() {
+            assert!(
+                result_properties.contains_key(key),
+                "undocumented result {key}"
+            );
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! JUnit XML output for CI test-report viewers
+
+use std::fmt::Write;
+
+use crate::output::{escape_xml, group_by};
+use crate::types::AnalysisResult;
+
+/// Builds a JUnit XML report with one test suite per file and one test case per function
+///
+/// Test cases are named after the canonical signature of their function, e.g.
+/// `setPool(address,uint256)`.
+///
+/// Each missing validation becomes a `<failure>` of its function's test case, so fully
+/// validated functions show up as passing tests.
+pub fn to_junit(results: &[AnalysisResult]) -> String {
+    let files = group_by(results, |result| result.file_path.as_str());
+
+    let failing = |results: &[&AnalysisResult]| {
+        results
+            .iter()
+            .filter(|result| !result.findings.is_empty())
+            .count()
+    };
+    let all: Vec<&AnalysisResult> = results.iter().collect();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"construstor\" tests=\"{}\" failures=\"{}\">",
+        all.len(),
+        failing(&all)
+    );
+
+    for (path, file_results) in &files {
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"0\">",
+            escape_xml(path),
+            file_results.len(),
+            failing(file_results)
+        );
+
+        for result in file_results {
+            let classname = result.contract_name.as_deref().unwrap_or(&result.file_name);
+            // The canonical signature stays on one line and survives reformatting, so
+            // viewers can track the test case across runs
+            let name = result.canonical_signature();
+            let _ = write!(
+                xml,
+                "    <testcase classname=\"{}\" name=\"{}\" file=\"{}\" line=\"{}\"",
+                escape_xml(classname),
+                escape_xml(&name),
+                escape_xml(&result.file_path),
+                result.line
+            );
+
+            if result.findings.is_empty() {
+                xml.push_str("/>\n");
+                continue;
+            }
+
+            xml.push_str(">\n");
+            for finding in &result.findings {
+                let message = result.finding_message(finding);
+                let _ = writeln!(
+                    xml,
+                    "      <failure message=\"{}\" type=\"{}\">{} [{}: {}] at {}:{}:{} (fingerprint {})</failure>",
+                    escape_xml(&message),
+                    finding.rule,
+                    escape_xml(&message),
+                    finding.severity,
+                    finding.rule,
+                    escape_xml(&result.file_path),
+                    finding.line,
+                    finding.column,
+                    finding.fingerprint
+                );
+            }
+            xml.push_str("    </testcase>\n");
+        }
+
+        xml.push_str("  </testsuite>\n");
+    }
+
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConstructorAnalyzer;
+
+    #[test]
+    fn test_junit_marks_validated_functions_as_passing() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let results = analyzer.analyze_source(
+            r#"contract Vault {
+                constructor(
+                    address _owner,
+                    uint256 _fee
+                ) { require(_owner != address(0)); }
+                function initialize(address _a, address _b) external { a = _a; b = _b; }
+            }"#,
+            "Vault.sol",
+            false,
+        );
+
+        let xml = to_junit(&results);
+
+        assert!(xml.contains("<testsuites name=\"construstor\" tests=\"2\" failures=\"1\">"));
+        assert!(xml.contains(
+            "<testcase classname=\"Vault\" name=\"constructor(address,uint256)\" file=\"Vault.sol\" line=\"2\"/>"
+        ));
+        assert_eq!(xml.matches("<failure ").count(), 2);
+        assert!(xml.contains(
+            "message=\"Parameter &apos;_b&apos; of initialize function in Vault is not checked against address(0)\""
+        ));
+    }
+}
@@ -0,0 +1,224 @@
+//! Markdown report for pull request comments
+
+use std::fmt::Write;
+
+use crate::output::group_by;
+use crate::types::{AnalysisResult, Finding, Summary, ValidationCounts};
+
+/// Label used for functions declared outside any contract
+const FILE_LEVEL: &str = "(file level)";
+
+/// Builds a Markdown report with a summary table and findings grouped by file and contract
+///
+/// Each contract is a collapsible `<details>` section so large reports stay readable in
+/// review comments. Findings suppressed by comments are counted in the summary and, with
+/// `show_suppressed`, listed in the findings tables.
+pub fn to_markdown(results: &[AnalysisResult], show_suppressed: bool) -> String {
+    let summary = Summary::from_results(results);
+    let mut md = String::from("## Construstor zero address report\n\n");
+
+    md.push_str("| | Functions | Fully validated | Partially validated | Not validated |\n");
+    md.push_str("| --- | ---: | ---: | ---: | ---: |\n");
+    let mut row = |label: &str, counts: &ValidationCounts| {
+        let _ = writeln!(
+            md,
+            "| {} | {} | {} | {} | {} |",
+            label,
+            counts.with_address_arguments,
+            counts.fully_validated,
+            counts.partially_validated,
+            counts.not_validated
+        );
+    };
+    row("**With address arguments**", &summary.overall);
+    row("Permissionless", &summary.permissionless);
+    row("Trusted caller", &summary.trusted_caller);
+    // Like the terminal summary, only break the counts down when files differ in origin
+    if summary.by_origin.len() > 1 {
+        for (origin, counts) in &summary.by_origin {
+            row(&format!("Origin: {}", origin.label()), counts);
+        }
+    }
+    let _ = write!(
+        md,
+        "\n{} function(s) analyzed, {} finding(s)",
+        summary.total_functions,
+        results.iter().map(|r| r.findings.len()).sum::<usize>()
+    );
+    if summary.suppressed_findings > 0 {
+        let _ = write!(
+            md,
+            ", {} suppressed by comment",
+            summary.suppressed_findings
+        );
+    }
+    md.push_str(".\n\n");
+
+    let files = group_by(
+        results.iter().filter(|result| {
+            !result.findings.is_empty() || show_suppressed && !result.suppressed_findings.is_empty()
+        }),
+        |result| result.file_path.as_str(),
+    );
+
+    if files.is_empty() {
+        md.push_str("✅ No missing zero address validations.\n");
+        return md;
+    }
+
+    for (path, file_results) in files {
+        let _ = writeln!(md, "### `{path}`\n");
+
+        let contracts = group_by(file_results, |result| {
+            result.contract_name.as_deref().unwrap_or(FILE_LEVEL)
+        });
+        for (contract, contract_results) in contracts {
+            let findings: usize = contract_results.iter().map(|r| r.findings.len()).sum();
+            let _ = writeln!(
+                md,
+                "<details>\n<summary><b>{}</b>: {} finding(s) in {} function(s)</summary>\n",
+                contract,
+                findings,
+                contract_results.len()
+            );
+
+            for result in contract_results {
+                // The canonical signature keeps multi-line parameter lists on the heading line
+                let _ = writeln!(
+                    md,
+                    "#### `{}`: {}\n",
+                    result.canonical_signature(),
+                    result.function_type
+                );
+                if result.trusted_caller {
+                    let _ = writeln!(
+                        md,
+                        "Trusted caller only: `{}`\n",
+                        result.access_controls.join("`, `")
+                    );
+                }
+
+                md.push_str("| Parameter | Severity | Rule | Usage | Line | Fingerprint |\n");
+                md.push_str("| --- | --- | --- | --- | ---: | --- |\n");
+                for finding in &result.findings {
+                    write_finding_row(&mut md, finding, false);
+                }
+                if show_suppressed {
+                    for finding in &result.suppressed_findings {
+                        write_finding_row(&mut md, finding, true);
+                    }
+                }
+
+                let _ = writeln!(md, "\n```solidity\n{}\n```\n", result.code);
+            }
+
+            md.push_str("</details>\n\n");
+        }
+    }
+
+    md
+}
+
+/// Writes a row of a findings table, marking findings suppressed by a comment
+fn write_finding_row(md: &mut String, finding: &Finding, suppressed: bool) {
+    let usage: Vec<&str> = finding
+        .usage
+        .iter()
+        .map(|usage| usage.description())
+        .collect();
+    let _ = writeln!(
+        md,
+        "| `{}`{} | {} | `{}` | {} | {} | `{}` |",
+        finding.parameter,
+        if suppressed { " (suppressed)" } else { "" },
+        finding.severity,
+        finding.rule,
+        usage.join(", "),
+        finding.line,
+        finding.fingerprint
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConstructorAnalyzer;
+    use crate::types::FileOrigin;
+
+    #[test]
+    fn test_markdown_groups_findings_by_file_and_contract() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let results = analyzer.analyze_source(
+            r#"contract Vault {
+                constructor(address _owner) { require(_owner != address(0)); }
+                function initialize(address _a) external { a = _a; }
+            }"#,
+            "Vault.sol",
+            false,
+        );
+
+        let md = to_markdown(&results, false);
+
+        assert!(md.contains("| **With address arguments** | 2 | 1 | 0 | 1 |"));
+        assert!(md.contains("### `Vault.sol`"));
+        assert!(md.contains("<summary><b>Vault</b>: 1 finding(s) in 1 function(s)</summary>"));
+        assert!(md.contains("| `_a` | high | `initializer-state` | stored to state | 3 |"));
+        assert!(md.contains("```solidity\nfunction initialize(address _a) {"));
+        assert!(!md.contains("constructor(address _owner) {"));
+    }
+
+    #[test]
+    fn test_markdown_heading_uses_canonical_signature() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let results = analyzer.analyze_source(
+            r#"contract Vault {
+                constructor(
+                    address _owner,
+                    uint256 _fee
+                ) { owner = _owner; }
+            }"#,
+            "Vault.sol",
+            false,
+        );
+
+        let md = to_markdown(&results, false);
+
+        assert!(md.contains("#### `constructor(address,uint256)`: constructor\n"));
+    }
+
+    #[test]
+    fn test_markdown_counts_and_lists_suppressed_findings() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let results = analyzer.analyze_source(
+            r#"contract Vault {
+                // construstor-disable-next-line zero-address:_owner
+                constructor(address _owner) { owner = _owner; }
+            }"#,
+            "Vault.sol",
+            false,
+        );
+
+        let hidden = to_markdown(&results, false);
+        assert!(hidden.contains("1 function(s) analyzed, 0 finding(s), 1 suppressed by comment."));
+        assert!(hidden.contains("✅ No missing zero address validations."));
+
+        let shown = to_markdown(&results, true);
+        assert!(shown.contains("| `_owner` (suppressed) | high | `initializer-state` |"));
+    }
+
+    #[test]
+    fn test_markdown_summary_breaks_down_mixed_origins() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let source = "contract Vault { constructor(address _owner) { owner = _owner; } }";
+        let mut results = analyzer.analyze_source(source, "src/Vault.sol", false);
+        assert!(!to_markdown(&results, false).contains("Origin:"));
+
+        let mut dependency = analyzer.analyze_source(source, "lib/Vault.sol", false);
+        dependency[0].origin = FileOrigin::Dependency;
+        results.extend(dependency);
+        let md = to_markdown(&results, false);
+
+        assert!(md.contains("| Origin: First-party | 1 | 0 | 0 | 1 |"));
+        assert!(md.contains("| Origin: Dependency | 1 | 0 | 0 | 1 |"));
+    }
+}
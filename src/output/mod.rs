@@ -0,0 +1,194 @@
+//! Machine-readable report formats
+
+pub mod compact;
+pub mod csv;
+pub mod github;
+pub mod gitlab;
+pub mod html;
+pub mod json;
+pub mod junit;
+pub mod markdown;
+pub mod ndjson;
+pub mod sarif;
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use serde_json::Value;
+
+use crate::baseline::BaselineComparison;
+use crate::types::{AnalysisResult, FileError};
+
+/// Format in which results are reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Colored, human-readable terminal output
+    #[default]
+    Text,
+    /// Versioned JSON report with metadata, summary and results, without code
+    Json,
+    /// SARIF 2.1.0 log for code-scanning dashboards
+    Sarif,
+    /// JUnit XML report for CI test-report viewers
+    Junit,
+    /// Markdown report for pull request comments
+    Markdown,
+    /// Self-contained HTML report for audit hand-offs
+    Html,
+    /// CSV with one row per address parameter
+    Csv,
+    /// GitHub Actions workflow commands for inline annotations
+    Github,
+    /// GitLab Code Quality report for merge request widgets
+    Gitlab,
+    /// Compiler-style one-line findings for editors and grep
+    Compact,
+    /// Newline-delimited JSON, streamed as files are analyzed
+    Ndjson,
+}
+
+impl OutputFormat {
+    /// Names accepted by `--format`
+    pub const NAMES: [&'static str; 11] = [
+        "text", "json", "sarif", "junit", "markdown", "html", "csv", "github", "gitlab", "compact",
+        "ndjson",
+    ];
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            OutputFormat::Text => "text",
+            OutputFormat::Json => "json",
+            OutputFormat::Sarif => "sarif",
+            OutputFormat::Junit => "junit",
+            OutputFormat::Markdown => "markdown",
+            OutputFormat::Html => "html",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Github => "github",
+            OutputFormat::Gitlab => "gitlab",
+            OutputFormat::Compact => "compact",
+            OutputFormat::Ndjson => "ndjson",
+        })
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "sarif" => Ok(OutputFormat::Sarif),
+            "junit" => Ok(OutputFormat::Junit),
+            "markdown" => Ok(OutputFormat::Markdown),
+            "html" => Ok(OutputFormat::Html),
+            "csv" => Ok(OutputFormat::Csv),
+            "github" => Ok(OutputFormat::Github),
+            "gitlab" => Ok(OutputFormat::Gitlab),
+            "compact" => Ok(OutputFormat::Compact),
+            "ndjson" => Ok(OutputFormat::Ndjson),
+            _ => Err(format!(
+                "unknown format '{s}', expected one of: {}",
+                OutputFormat::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+/// Information about a run that some formats report alongside the results
+#[derive(Debug, Clone, Default)]
+pub struct ReportContext {
+    /// Path that was analyzed
+    pub root: String,
+    /// Options the run was configured with
+    pub options: Value,
+    /// Files that could not be analyzed
+    pub errors: Vec<FileError>,
+    /// Also list findings suppressed by comments, in formats that can mark them
+    pub show_suppressed: bool,
+    /// How the results compare with a baseline, when one was given
+    pub baseline: Option<BaselineComparison>,
+}
+
+/// Renders results in a machine-readable format
+///
+/// The text format is printed directly by [`ResultPrinter`] and renders as an empty string.
+///
+/// [`ResultPrinter`]: crate::printer::ResultPrinter
+pub fn render(
+    format: OutputFormat,
+    results: &[AnalysisResult],
+    context: &ReportContext,
+) -> Result<String, Box<dyn Error>> {
+    Ok(match format {
+        OutputFormat::Text => String::new(),
+        OutputFormat::Json => {
+            serde_json::to_string_pretty(&json::to_json(results, context))? + "\n"
+        }
+        OutputFormat::Sarif => {
+            serde_json::to_string_pretty(&sarif::to_sarif(results, context.show_suppressed))? + "\n"
+        }
+        OutputFormat::Junit => junit::to_junit(results),
+        OutputFormat::Markdown => markdown::to_markdown(results, context.show_suppressed),
+        OutputFormat::Html => html::to_html(results, context.show_suppressed),
+        OutputFormat::Csv => csv::to_csv(results),
+        OutputFormat::Github => github::to_github(results),
+        OutputFormat::Gitlab => serde_json::to_string_pretty(&gitlab::to_gitlab(results))? + "\n",
+        OutputFormat::Compact => compact::to_compact(results),
+        OutputFormat::Ndjson => ndjson::to_ndjson(results, context)?,
+    })
+}
+
+/// Escapes text for use in XML or HTML content and attribute values
+pub(crate) fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Groups results by a key, keeping the order in which keys first appear
+pub(crate) fn group_by<'a, K: PartialEq>(
+    results: impl IntoIterator<Item = &'a AnalysisResult>,
+    key: impl Fn(&'a AnalysisResult) -> K,
+) -> Vec<(K, Vec<&'a AnalysisResult>)> {
+    let mut groups: Vec<(K, Vec<&'a AnalysisResult>)> = Vec::new();
+    for result in results {
+        let result_key = key(result);
+        match groups
+            .iter_mut()
+            .find(|(group_key, _)| *group_key == result_key)
+        {
+            Some((_, group)) => group.push(result),
+            None => groups.push((result_key, vec![result])),
+        }
+    }
+    groups
+}
+
+/// Normalizes a file path to forward slashes without a leading `./`
+pub(crate) fn report_path(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    path.strip_prefix("./").unwrap_or(&path).to_string()
+}
+
+/// Converts a file path into a URI reference with forward slashes
+pub(crate) fn artifact_uri(path: &str) -> String {
+    let path = report_path(path);
+    if path.starts_with('/') {
+        format!("file://{path}")
+    } else {
+        path
+    }
+}
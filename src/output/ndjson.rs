@@ -0,0 +1,149 @@
+//! Newline-delimited JSON, written record by record as files are analyzed
+
+use std::io::{self, Write};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::ReportContext;
+use super::json::{SCHEMA_VERSION, Tool, timestamp};
+use crate::baseline::BaselineComparison;
+use crate::types::{AnalysisResult, AnalysisResultJson, FileError, Summary};
+
+/// One line of the stream, tagged by its `type` field
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Record<'a> {
+    /// First line: run metadata, as in the JSON report envelope
+    Header {
+        schema_version: &'static str,
+        tool: Tool,
+        root: &'a str,
+        generated_at: String,
+        options: &'a Value,
+    },
+    /// One analyzed function
+    Result(Box<AnalysisResultJson>),
+    /// A file that could not be analyzed
+    Error(&'a FileError),
+    /// How the results compare with the baseline, when one was given
+    Baseline(&'a BaselineComparison),
+    /// Last line: totals over every result written
+    Summary(&'a Summary),
+}
+
+/// Writes results as newline-delimited JSON as soon as they are handed over
+///
+/// The stream starts with a `header` record, then has one `result` record per function
+/// and one `error` record per file that could not be analyzed, and ends with an optional
+/// `baseline` record and a `summary` record written by [`NdjsonWriter::finish`].
+pub struct NdjsonWriter<W: Write> {
+    writer: W,
+    summary: Summary,
+}
+
+impl<W: Write> NdjsonWriter<W> {
+    /// Starts the stream by writing the header record
+    pub fn new(mut writer: W, context: &ReportContext) -> io::Result<Self> {
+        write_record(
+            &mut writer,
+            &Record::Header {
+                schema_version: SCHEMA_VERSION,
+                tool: Tool::CURRENT,
+                root: &context.root,
+                generated_at: timestamp(),
+                options: &context.options,
+            },
+        )?;
+        Ok(Self {
+            writer,
+            summary: Summary::default(),
+        })
+    }
+
+    /// Writes the results of one file and flushes them
+    pub fn write_results(&mut self, results: &[AnalysisResult]) -> io::Result<()> {
+        for result in results {
+            write_record(&mut self.writer, &Record::Result(Box::new(result.into())))?;
+        }
+        self.summary.merge(&Summary::from_results(results));
+        self.writer.flush()
+    }
+
+    /// Writes a file that could not be analyzed
+    pub fn write_error(&mut self, error: &FileError) -> io::Result<()> {
+        write_record(&mut self.writer, &Record::Error(error))?;
+        self.writer.flush()
+    }
+
+    /// Writes how the results compare with the baseline
+    pub fn write_baseline(&mut self, comparison: &BaselineComparison) -> io::Result<()> {
+        write_record(&mut self.writer, &Record::Baseline(comparison))?;
+        self.writer.flush()
+    }
+
+    /// Ends the stream with the summary record and returns the writer
+    pub fn finish(mut self) -> io::Result<W> {
+        write_record(&mut self.writer, &Record::Summary(&self.summary))?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Writes a record on a line of its own
+fn write_record(writer: &mut impl Write, record: &Record) -> io::Result<()> {
+    serde_json::to_writer(&mut *writer, record)?;
+    writer.write_all(b"\n")
+}
+
+/// Renders results as a complete stream
+pub fn to_ndjson(results: &[AnalysisResult], context: &ReportContext) -> io::Result<String> {
+    let mut writer = NdjsonWriter::new(Vec::new(), context)?;
+    writer.write_results(results)?;
+    for error in &context.errors {
+        writer.write_error(error)?;
+    }
+    if let Some(comparison) = &context.baseline {
+        writer.write_baseline(comparison)?;
+    }
+    let bytes = writer.finish()?;
+    Ok(String::from_utf8(bytes).expect("serde_json writes UTF-8"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConstructorAnalyzer;
+
+    #[test]
+    fn test_ndjson_writes_one_record_per_line() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let context = ReportContext {
+            root: "contracts".to_string(),
+            ..ReportContext::default()
+        };
+        let mut writer = NdjsonWriter::new(Vec::new(), &context).unwrap();
+        for source in [
+            "contract A {\n    constructor(address _a) {}\n}\n",
+            "contract B {\n    constructor(address _b) { require(_b != address(0)); }\n}\n",
+        ] {
+            let results = analyzer.analyze_source(source, "X.sol", false);
+            writer.write_results(&results).unwrap();
+        }
+        let output = String::from_utf8(writer.finish().unwrap()).unwrap();
+
+        let records: Vec<Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let types: Vec<&str> = records
+            .iter()
+            .map(|record| record["type"].as_str().unwrap())
+            .collect();
+        assert_eq!(types, ["header", "result", "result", "summary"]);
+        assert_eq!(records[0]["root"], "contracts");
+        assert_eq!(records[1]["missing_validations"][0], "_a");
+        assert_eq!(records[3]["total_functions"], 2);
+        assert_eq!(records[3]["fully_validated"], 1);
+    }
+}
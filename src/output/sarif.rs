@@ -0,0 +1,209 @@
+//! SARIF 2.1.0 output for code-scanning dashboards
+
+use serde_json::{Value, json};
+
+use super::artifact_uri;
+use crate::severity::{Severity, SeverityRule};
+use crate::types::{AnalysisResult, Finding};
+
+/// Schema URI written to the `$schema` property
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Prefix of every rule identifier
+const RULE_PREFIX: &str = "zero-address";
+
+/// Builds a SARIF log with one result per missing validation
+///
+/// With `show_suppressed`, findings suppressed by comments are included as results with
+/// an in-source suppression.
+pub fn to_sarif(results: &[AnalysisResult], show_suppressed: bool) -> Value {
+    let rules: Vec<Value> = SeverityRule::ALL.iter().map(rule_descriptor).collect();
+
+    let mut sarif_results: Vec<Value> = results
+        .iter()
+        .flat_map(|result| {
+            result
+                .findings
+                .iter()
+                .map(move |finding| sarif_result(result, finding))
+        })
+        .collect();
+    if show_suppressed {
+        sarif_results.extend(results.iter().flat_map(|result| {
+            result.suppressed_findings.iter().map(move |finding| {
+                let mut suppressed = sarif_result(result, finding);
+                suppressed["suppressions"] = json!([{ "kind": "inSource" }]);
+                suppressed
+            })
+        }));
+    }
+
+    json!({
+        "$schema": SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                }
+            },
+            "results": sarif_results,
+        }]
+    })
+}
+
+/// Describes a severity rule as a SARIF reporting descriptor
+fn rule_descriptor(rule: &SeverityRule) -> Value {
+    let severity = rule.default_severity();
+    json!({
+        "id": rule_id(*rule),
+        "name": rule.id(),
+        "shortDescription": { "text": rule.description() },
+        "fullDescription": {
+            "text": format!(
+                "{}. Address parameters should be checked against address(0) before use.",
+                rule.description()
+            )
+        },
+        "defaultConfiguration": { "level": level(severity) },
+        "properties": {
+            "tags": ["security"],
+            "security-severity": security_severity(severity),
+        }
+    })
+}
+
+/// Converts one finding into a SARIF result
+fn sarif_result(result: &AnalysisResult, finding: &Finding) -> Value {
+    let usage: Vec<&str> = finding
+        .usage
+        .iter()
+        .map(|usage| usage.description())
+        .collect();
+
+    json!({
+        "ruleId": rule_id(finding.rule),
+        "ruleIndex": SeverityRule::ALL.iter().position(|rule| *rule == finding.rule),
+        "level": level(finding.severity),
+        "message": {
+            "text": format!("{} ({}).", result.finding_message(finding), usage.join(", "))
+        },
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": { "uri": artifact_uri(&result.file_path) },
+                "region": {
+                    "startLine": finding.line,
+                    "startColumn": finding.column,
+                }
+            },
+            "logicalLocations": [{
+                "fullyQualifiedName": result.qualified_name(),
+                "kind": "function",
+            }]
+        }],
+        "partialFingerprints": {
+            "construstor/v2": finding.fingerprint,
+        },
+        "properties": {
+            "severity": finding.severity,
+            "parameter": finding.parameter,
+            "usage": finding.usage,
+            "trustedCaller": result.trusted_caller,
+        }
+    })
+}
+
+/// Returns the SARIF rule identifier of a severity rule
+fn rule_id(rule: SeverityRule) -> String {
+    format!("{RULE_PREFIX}/{}", rule.id())
+}
+
+/// Maps a severity to a SARIF result level
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical | Severity::High => "error",
+        Severity::Medium => "warning",
+        Severity::Low | Severity::Info => "note",
+    }
+}
+
+/// Maps a severity to the numeric score code-scanning dashboards sort by
+fn security_severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "9.5",
+        Severity::High => "8.0",
+        Severity::Medium => "5.5",
+        Severity::Low => "3.0",
+        Severity::Info => "1.0",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConstructorAnalyzer;
+
+    #[test]
+    fn test_sarif_reports_each_finding_with_location() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let results = analyzer.analyze_source(
+            "contract Vault {\n    address owner;\n    constructor(address _owner) {\n        owner = _owner;\n    }\n}\n",
+            "Vault.sol",
+            false,
+        );
+
+        let sarif = to_sarif(&results, false);
+        let result = &sarif["runs"][0]["results"][0];
+
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(result["ruleId"], "zero-address/initializer-state");
+        assert_eq!(result["level"], "error");
+        let region = &result["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(region["startLine"], 3);
+        assert_eq!(region["startColumn"], 25);
+        assert_eq!(
+            result["locations"][0]["logicalLocations"][0]["fullyQualifiedName"],
+            "Vault.constructor"
+        );
+        assert_eq!(
+            result["partialFingerprints"]["construstor/v2"],
+            results[0].findings[0].fingerprint.as_str()
+        );
+    }
+
+    #[test]
+    fn test_sarif_uses_file_uris_for_absolute_paths() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let results = analyzer.analyze_source(
+            "contract Vault {\n    address owner;\n    constructor(address _owner) {\n        owner = _owner;\n    }\n}\n",
+            "/work/src/Vault.sol",
+            false,
+        );
+
+        let sarif = to_sarif(&results, false);
+
+        assert_eq!(
+            sarif["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"]
+                ["uri"],
+            "file:///work/src/Vault.sol"
+        );
+    }
+
+    #[test]
+    fn test_sarif_without_findings_is_a_valid_log() {
+        let sarif = to_sarif(&[], true);
+
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(sarif["runs"][0]["results"], json!([]));
+        assert_eq!(
+            sarif["runs"][0]["tool"]["driver"]["rules"]
+                .as_array()
+                .unwrap()
+                .len(),
+            SeverityRule::ALL.len()
+        );
+    }
+}
@@ -0,0 +1,129 @@
+//! Failure policy deciding when findings should fail the run
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::severity::Severity;
+use crate::types::AnalysisResult;
+
+/// Exit code when the analysis succeeded and no findings breach the failure policy
+pub const EXIT_CLEAN: i32 = 0;
+/// Exit code when the analysis succeeded but findings breach the failure policy
+pub const EXIT_FINDINGS: i32 = 1;
+/// Exit code when the analysis could not be completed
+pub const EXIT_ERROR: i32 = 2;
+
+/// Which findings count against the failure policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FailOn {
+    /// Findings never fail the run
+    None,
+    /// Every finding counts
+    #[default]
+    Any,
+    /// Only findings at or above this severity count
+    Severity(Severity),
+}
+
+impl fmt::Display for FailOn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FailOn::None => f.write_str("none"),
+            FailOn::Any => f.write_str("any"),
+            FailOn::Severity(severity) => write!(f, "{severity}"),
+        }
+    }
+}
+
+impl FromStr for FailOn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" => Ok(FailOn::None),
+            "any" => Ok(FailOn::Any),
+            other => other
+                .parse()
+                .map(FailOn::Severity)
+                .map_err(|_| format!("expected a severity, 'any' or 'none', got '{s}'")),
+        }
+    }
+}
+
+/// Decides whether the findings of a run should fail it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FailPolicy {
+    /// Which findings count
+    pub fail_on: FailOn,
+    /// Number of counted findings tolerated before the run fails
+    pub max_findings: usize,
+}
+
+impl FailPolicy {
+    /// Counts the findings that count against the policy
+    pub fn counted_findings(&self, results: &[AnalysisResult]) -> usize {
+        let findings = results.iter().flat_map(|result| &result.findings);
+        match self.fail_on {
+            FailOn::None => 0,
+            FailOn::Any => findings.count(),
+            FailOn::Severity(min) => findings.filter(|finding| finding.severity >= min).count(),
+        }
+    }
+
+    /// Returns the process exit code for a successful analysis
+    pub fn exit_code(&self, results: &[AnalysisResult]) -> i32 {
+        self.exit_code_for_count(self.counted_findings(results))
+    }
+
+    /// Returns the process exit code given the number of counted findings, for callers
+    /// that do not keep every result
+    pub fn exit_code_for_count(&self, counted_findings: usize) -> i32 {
+        if self.fail_on != FailOn::None && counted_findings > self.max_findings {
+            EXIT_FINDINGS
+        } else {
+            EXIT_CLEAN
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConstructorAnalyzer;
+
+    #[test]
+    fn test_exit_code_follows_fail_on_and_max_findings() {
+        let analyzer = ConstructorAnalyzer::new().unwrap();
+        let results = analyzer.analyze_source(
+            r#"contract A {
+                constructor(address _a, address _b) { a = _a; emit B(_b); }
+            }"#,
+            "A.sol",
+            false,
+        );
+
+        let policy = |fail_on, max_findings| FailPolicy {
+            fail_on,
+            max_findings,
+        };
+        assert_eq!(policy(FailOn::Any, 0).exit_code(&results), EXIT_FINDINGS);
+        assert_eq!(policy(FailOn::Any, 2).exit_code(&results), EXIT_CLEAN);
+        assert_eq!(policy(FailOn::None, 0).exit_code(&results), EXIT_CLEAN);
+        assert_eq!(
+            policy(FailOn::Severity(Severity::High), 0).exit_code(&results),
+            EXIT_FINDINGS
+        );
+        assert_eq!(
+            policy(FailOn::Severity(Severity::Critical), 0).exit_code(&results),
+            EXIT_CLEAN
+        );
+    }
+
+    #[test]
+    fn test_parse_fail_on() {
+        assert_eq!("none".parse(), Ok(FailOn::None));
+        assert_eq!("ANY".parse(), Ok(FailOn::Any));
+        assert_eq!("high".parse(), Ok(FailOn::Severity(Severity::High)));
+        assert!("sometimes".parse::<FailOn>().is_err());
+    }
+}
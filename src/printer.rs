@@ -2,33 +2,47 @@
 
 use colored::*;
 
-use crate::types::{AnalysisResult, FunctionType, ValidationType};
+use crate::baseline::BaselineComparison;
+use crate::severity::Severity;
+use crate::types::{
+    AnalysisResult, Finding, FunctionType, Summary, ValidationCounts, ValidationType,
+};
 
 /// Pretty printer for analysis results
 pub struct ResultPrinter;
 
 impl ResultPrinter {
     /// Prints analysis results with colored output
-    pub fn print_results(results: &[AnalysisResult]) {
+    ///
+    /// Findings suppressed by comments are only listed with `show_suppressed`.
+    pub fn print_results(results: &[AnalysisResult], show_suppressed: bool) {
         if results.is_empty() {
             println!("{}", "No functions with address parameters found.".yellow());
             return;
         }
 
         for result in results {
-            Self::print_single_result(result);
+            Self::print_single_result(result, show_suppressed);
         }
     }
 
     /// Prints a single analysis result
-    fn print_single_result(result: &AnalysisResult) {
+    fn print_single_result(result: &AnalysisResult, show_suppressed: bool) {
         let function_name = match &result.function_type {
             FunctionType::Constructor => "Constructor".green(),
             FunctionType::Initialize => "Initialize function".cyan(),
             FunctionType::Regular(name) => format!("Function '{}'", name).magenta(),
+            FunctionType::Modifier(name) => format!("Modifier '{}'", name).magenta(),
+            FunctionType::FreeFunction(name) => format!("Free function '{}'", name).magenta(),
+            FunctionType::LibraryFunction(name) => format!("Library function '{}'", name).magenta(),
+            FunctionType::Fallback => "Fallback function".magenta(),
+            FunctionType::Receive => "Receive function".magenta(),
         };
 
-        println!("{} in {}:", function_name, result.file_name);
+        match &result.contract_name {
+            Some(contract) => println!("{} in {} ({}):", function_name, result.file_name, contract),
+            None => println!("{} in {}:", function_name, result.file_name),
+        }
 
         if result.address_arguments.is_empty() {
             println!("{}", "ℹ️  No address arguments found".blue());
@@ -69,29 +83,112 @@ impl ResultPrinter {
                     );
                 }
 
-                for var in &result.validated_variables {
-                    println!("    {} Checking variable: {}", "→".blue(), var.yellow());
+                if result
+                    .validation_types
+                    .contains(&ValidationType::ValidatorCall)
+                {
+                    println!("  {} Call to a validator function", "•".green());
                 }
-            }
 
-            if !result.missing_validations.is_empty() {
-                println!("{}", "❌ Missing zero address validation for:".red());
-                for missing_arg in &result.missing_validations {
-                    println!("    {} Argument: {}", "⚠️".red(), missing_arg.yellow());
+                for evidence in &result.validation_evidence {
+                    println!(
+                        "    {} Checking variable: {} (line {}: {})",
+                        "→".blue(),
+                        evidence.variable.yellow(),
+                        evidence.line,
+                        evidence.source
+                    );
                 }
-            } else if !result.address_arguments.is_empty() && !result.validation_types.is_empty() {
+            }
+
+            if !result.zero_address_allowed.is_empty() {
                 println!(
                     "{}",
-                    "✅ All address arguments are validated!".green().bold()
+                    format!(
+                        "ℹ️  May be address(0) by design (NatSpec): {}",
+                        result.zero_address_allowed.join(", ")
+                    )
+                    .blue()
                 );
-            } else if !result.address_arguments.is_empty() {
+            }
+
+            // Unchecked arguments that neither NatSpec nor a finding accounts for were accepted
+            // by a suppression comment or a baseline
+            let accepted = result.address_arguments.iter().any(|(_, name)| {
+                !result.validated_variables.contains(name)
+                    && !result.zero_address_allowed.contains(name)
+                    && !result.findings.iter().any(|f| f.parameter == *name)
+            });
+            if !result.findings.is_empty() {
+                println!("{}", "❌ Missing zero address validation for:".red());
+                for finding in &result.findings {
+                    println!(
+                        "    {} Argument: {} {} ({}) {}",
+                        "⚠️".red(),
+                        finding.parameter.yellow(),
+                        Self::severity_label(finding),
+                        Self::usage_label(finding),
+                        finding.fingerprint.dimmed()
+                    );
+                }
+            } else if accepted {
                 println!(
                     "{}",
-                    "❌ No zero address validation detected for any argument".red()
+                    "✅ Unchecked arguments are accepted by a suppression comment or the baseline"
+                        .green()
                 );
+            } else if result.zero_address_allowed.is_empty() {
+                if result.validation_types.is_empty() {
+                    println!(
+                        "{}",
+                        "❌ No zero address validation detected for any argument".red()
+                    );
+                } else {
+                    println!(
+                        "{}",
+                        "✅ All address arguments are validated!".green().bold()
+                    );
+                }
+            }
+
+            if show_suppressed && !result.suppressed_findings.is_empty() {
+                println!("{}", "🔇 Suppressed by comment:".dimmed());
+                for finding in &result.suppressed_findings {
+                    println!(
+                        "    {} Argument: {} {} ({}) {}",
+                        "•".dimmed(),
+                        finding.parameter.yellow(),
+                        Self::severity_label(finding),
+                        Self::usage_label(finding),
+                        finding.fingerprint.dimmed()
+                    );
+                }
             }
         }
 
+        let attributes: Vec<&str> = result
+            .visibility
+            .map(|visibility| visibility.as_str())
+            .into_iter()
+            .chain(result.mutability.keyword())
+            .chain(result.modifiers.iter().map(String::as_str))
+            .collect();
+        if !attributes.is_empty() {
+            println!(
+                "{}",
+                format!("Attributes: {}", attributes.join(" ")).yellow()
+            );
+        }
+        if result.trusted_caller {
+            println!(
+                "{}",
+                format!(
+                    "🔐 Trusted caller only: {}",
+                    result.access_controls.join(", ")
+                )
+                .blue()
+            );
+        }
         println!("{}", format!("Arguments: {}", result.arguments).yellow());
         println!("{}", "Code:".blue());
         // Print the code with proper indentation
@@ -101,47 +198,114 @@ impl ResultPrinter {
         println!("{}", "=".repeat(50));
     }
 
+    /// Describes how the parameter of a finding is used
+    fn usage_label(finding: &Finding) -> String {
+        let usage: Vec<&str> = finding
+            .usage
+            .iter()
+            .map(|usage| usage.description())
+            .collect();
+        usage.join(", ")
+    }
+
+    /// Formats the severity and rule of a finding, colored by severity
+    fn severity_label(finding: &Finding) -> ColoredString {
+        let label = format!(
+            "[{}: {}]",
+            finding.severity.as_str().to_uppercase(),
+            finding.rule
+        );
+        match finding.severity {
+            Severity::Critical => label.red().bold(),
+            Severity::High => label.red(),
+            Severity::Medium => label.yellow(),
+            Severity::Low => label.blue(),
+            Severity::Info => label.dimmed(),
+        }
+    }
+
     /// Prints a summary of all results
     pub fn print_summary(results: &[AnalysisResult]) {
         if results.is_empty() {
             return;
         }
 
-        let total_functions = results.len();
-        let functions_with_address_args: Vec<_> = results
-            .iter()
-            .filter(|r| !r.address_arguments.is_empty())
-            .collect();
-
-        let fully_validated = functions_with_address_args
-            .iter()
-            .filter(|r| r.missing_validations.is_empty())
-            .count();
-
-        let partially_validated = functions_with_address_args
-            .iter()
-            .filter(|r| !r.missing_validations.is_empty() && !r.validated_variables.is_empty())
-            .count();
-
-        let unvalidated = functions_with_address_args
-            .iter()
-            .filter(|r| r.validated_variables.is_empty())
-            .count();
+        let summary = Summary::from_results(results);
 
         println!("\n{}", "📊 Analysis Summary:".bold().blue());
         println!(
             "  Total functions analyzed: {}",
-            total_functions.to_string().yellow()
+            summary.total_functions.to_string().yellow()
         );
         println!(
             "  Functions with address arguments: {}",
-            functions_with_address_args.len().to_string().yellow()
+            summary.overall.with_address_arguments.to_string().yellow()
+        );
+        println!(
+            "  Fully validated: {}",
+            summary.overall.fully_validated.to_string().green()
         );
-        println!("  Fully validated: {}", fully_validated.to_string().green());
         println!(
             "  Partially validated: {}",
-            partially_validated.to_string().yellow()
+            summary.overall.partially_validated.to_string().yellow()
+        );
+        println!(
+            "  Not validated: {}",
+            summary.overall.not_validated.to_string().red()
+        );
+
+        Self::print_caller_counts("Permissionless", &summary.permissionless);
+        Self::print_caller_counts("Trusted caller", &summary.trusted_caller);
+        if summary.suppressed_findings > 0 {
+            println!(
+                "  Suppressed findings: {}",
+                summary.suppressed_findings.to_string().dimmed()
+            );
+        }
+
+        if summary.by_origin.len() > 1 {
+            println!("  {}", "By origin:".bold());
+            for (origin, counts) in &summary.by_origin {
+                Self::print_caller_counts(&format!("  {}", origin.label()), counts);
+            }
+        }
+    }
+
+    /// Prints how many findings a baseline hid and which of its findings were fixed
+    pub fn print_baseline(comparison: &BaselineComparison) {
+        println!(
+            "\n{} {} known finding(s) from {} not shown",
+            "📌 Baseline:".bold().blue(),
+            comparison.known.to_string().yellow(),
+            comparison.path
+        );
+        if !comparison.fixed.is_empty() {
+            println!(
+                "{}",
+                format!("✅ Fixed since the baseline ({}):", comparison.fixed.len()).green()
+            );
+            for entry in &comparison.fixed {
+                println!(
+                    "  {} {}: {} parameter {} ({})",
+                    "•".green(),
+                    entry.file,
+                    entry.function,
+                    entry.parameter.yellow(),
+                    entry.rule
+                );
+            }
+        }
+    }
+
+    /// Prints validation counts for one caller group of the summary
+    fn print_caller_counts(label: &str, counts: &ValidationCounts) {
+        println!(
+            "  {}: {} ({} fully, {} partially, {} not validated)",
+            label,
+            counts.with_address_arguments.to_string().yellow(),
+            counts.fully_validated.to_string().green(),
+            counts.partially_validated.to_string().yellow(),
+            counts.not_validated.to_string().red()
         );
-        println!("  Not validated: {}", unvalidated.to_string().red());
     }
 }
@@ -0,0 +1,158 @@
+//! Severity levels and the rules that assign them to missing validations
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// How serious a missing zero address validation is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    /// Names accepted on the command line, from least to most severe
+    pub const NAMES: [&'static str; 5] = ["info", "low", "medium", "high", "critical"];
+
+    /// Returns the lowercase name of this severity
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+            Severity::Critical => "critical",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "info" => Ok(Severity::Info),
+            "low" => Ok(Severity::Low),
+            "medium" => Ok(Severity::Medium),
+            "high" => Ok(Severity::High),
+            "critical" => Ok(Severity::Critical),
+            _ => Err(format!(
+                "unknown severity '{s}', expected one of: {}",
+                Severity::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+/// Context rule that decides the severity of a missing validation
+///
+/// When several rules apply to a parameter, the first one in [`SeverityRule::ALL`] wins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SeverityRule {
+    DelegatecallTarget,
+    CallTarget,
+    InitializerState,
+    AccessControlledSetter,
+    TokenRecipient,
+    StateWrite,
+    Unclassified,
+}
+
+impl SeverityRule {
+    /// Every rule, in precedence order
+    pub const ALL: [SeverityRule; 7] = [
+        SeverityRule::DelegatecallTarget,
+        SeverityRule::CallTarget,
+        SeverityRule::InitializerState,
+        SeverityRule::AccessControlledSetter,
+        SeverityRule::TokenRecipient,
+        SeverityRule::StateWrite,
+        SeverityRule::Unclassified,
+    ];
+
+    /// Returns the identifier used in output and overrides
+    pub fn id(&self) -> &'static str {
+        match self {
+            SeverityRule::DelegatecallTarget => "delegatecall-target",
+            SeverityRule::CallTarget => "call-target",
+            SeverityRule::InitializerState => "initializer-state",
+            SeverityRule::AccessControlledSetter => "access-controlled-setter",
+            SeverityRule::TokenRecipient => "token-recipient",
+            SeverityRule::StateWrite => "state-write",
+            SeverityRule::Unclassified => "unclassified",
+        }
+    }
+
+    /// Returns a one-line description of the context the rule matches
+    pub fn description(&self) -> &'static str {
+        match self {
+            SeverityRule::DelegatecallTarget => {
+                "Unchecked address is used as a delegatecall target"
+            }
+            SeverityRule::CallTarget => "Unchecked address is used as an external call target",
+            SeverityRule::InitializerState => {
+                "Constructor or initializer stores an unchecked address to state"
+            }
+            SeverityRule::AccessControlledSetter => {
+                "Access-controlled function stores an unchecked address to state"
+            }
+            SeverityRule::TokenRecipient => "Unchecked address receives tokens or ether",
+            SeverityRule::StateWrite => "Unchecked address is stored to state",
+            SeverityRule::Unclassified => "Unchecked address parameter",
+        }
+    }
+
+    /// Returns the severity assigned when no override is configured
+    pub fn default_severity(&self) -> Severity {
+        match self {
+            SeverityRule::DelegatecallTarget => Severity::Critical,
+            SeverityRule::CallTarget => Severity::High,
+            SeverityRule::InitializerState => Severity::High,
+            SeverityRule::AccessControlledSetter => Severity::Low,
+            SeverityRule::TokenRecipient => Severity::Medium,
+            SeverityRule::StateWrite => Severity::Medium,
+            SeverityRule::Unclassified => Severity::Low,
+        }
+    }
+}
+
+impl fmt::Display for SeverityRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+impl FromStr for SeverityRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SeverityRule::ALL
+            .into_iter()
+            .find(|rule| rule.id() == s)
+            .ok_or_else(|| {
+                let ids: Vec<&str> = SeverityRule::ALL.iter().map(|rule| rule.id()).collect();
+                format!("unknown rule '{s}', expected one of: {}", ids.join(", "))
+            })
+    }
+}
+
+/// Parses a `rule=severity` override
+pub fn parse_override(s: &str) -> Result<(SeverityRule, Severity), String> {
+    let (rule, severity) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected RULE=SEVERITY, got '{s}'"))?;
+    Ok((rule.trim().parse()?, severity.trim().parse()?))
+}
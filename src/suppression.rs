@@ -0,0 +1,185 @@
+//! Inline comments that suppress findings
+//!
+//! A `construstor-disable-next-line`, `construstor-disable-line` or
+//! `construstor-disable-file` comment suppresses the findings of the line after it, of its
+//! own line or of the whole file. Without a target it covers every finding; a
+//! `zero-address` target does the same, and `zero-address:_param` only covers the finding
+//! of that parameter. Text after `--` is a free-form justification.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::types::Finding;
+
+/// Name of the check as written in suppression comments
+pub const CHECK_NAME: &str = "zero-address";
+
+static DIRECTIVE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"construstor-disable-(next-line|line|file)\b([^\n]*)")
+        .expect("suppression directive regex is valid")
+});
+
+/// Which lines a suppression comment covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuppressionScope {
+    /// The line of the comment
+    Line,
+    /// The line after the comment
+    NextLine,
+    /// The whole file
+    File,
+}
+
+/// A suppression comment found in a source file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suppression {
+    /// Which lines the comment covers
+    pub scope: SuppressionScope,
+    /// 1-based line of the comment
+    pub line: usize,
+    /// Parameters the comment names; empty covers every parameter
+    pub parameters: Vec<String>,
+}
+
+impl Suppression {
+    /// Finds the suppression comments in `source`
+    ///
+    /// `masked` is the source with comments and strings blanked out, so that directives
+    /// written in code are not recognized.
+    pub(crate) fn find_all(source: &str, masked: &str) -> Vec<Self> {
+        DIRECTIVE_REGEX
+            .captures_iter(source)
+            .filter_map(|captures| {
+                let whole = captures.get(0)?;
+                if masked.as_bytes()[whole.start()] != b' ' {
+                    return None;
+                }
+                let scope = match &captures[1] {
+                    "next-line" => SuppressionScope::NextLine,
+                    "line" => SuppressionScope::Line,
+                    _ => SuppressionScope::File,
+                };
+                let targets = captures[2]
+                    .split("*/")
+                    .next()
+                    .and_then(|text| text.split("--").next())
+                    .unwrap_or_default();
+                Some(Self {
+                    scope,
+                    line: source[..whole.start()].matches('\n').count() + 1,
+                    parameters: parse_targets(targets)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns true if the comment covers `finding`, reported in a function declared on
+    /// `function_line`
+    pub fn covers(&self, finding: &Finding, function_line: usize) -> bool {
+        let line_matches = match self.scope {
+            SuppressionScope::File => true,
+            SuppressionScope::Line => finding.line == self.line || function_line == self.line,
+            SuppressionScope::NextLine => {
+                finding.line == self.line + 1 || function_line == self.line + 1
+            }
+        };
+        line_matches && (self.parameters.is_empty() || self.parameters.contains(&finding.parameter))
+    }
+}
+
+/// Parses the targets of a directive into the parameters it names
+///
+/// Returns `None` when the directive only names other checks, so it does not apply here.
+fn parse_targets(targets: &str) -> Option<Vec<String>> {
+    let targets: Vec<&str> = targets
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|target| !target.is_empty())
+        .collect();
+    if targets.is_empty() {
+        return Some(Vec::new());
+    }
+
+    let mut parameters = Vec::new();
+    let mut applies = false;
+    for target in targets {
+        match target.split_once(':') {
+            Some((CHECK_NAME, parameter)) => {
+                applies = true;
+                parameters.push(parameter.to_string());
+            }
+            None if target == CHECK_NAME => {
+                // An unscoped target covers every parameter
+                return Some(Vec::new());
+            }
+            _ => {}
+        }
+    }
+    applies.then_some(parameters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::mask_comments_and_strings;
+    use crate::severity::{Severity, SeverityRule};
+
+    fn finding(parameter: &str, line: usize) -> Finding {
+        Finding {
+            fingerprint: String::new(),
+            parameter: parameter.to_string(),
+            line,
+            column: 1,
+            severity: Severity::Medium,
+            rule: SeverityRule::StateWrite,
+            usage: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_find_all_parses_scopes_and_targets() {
+        let source = "\
+// construstor-disable-file zero-address:_a -- set later by governance
+/* construstor-disable-next-line zero-address:_b, zero-address:_c */
+construstor-disable-line;
+x; // construstor-disable-line other-check
+y; // construstor-disable-line
+";
+        let suppressions = Suppression::find_all(source, &mask_comments_and_strings(source));
+
+        assert_eq!(
+            suppressions,
+            vec![
+                Suppression {
+                    scope: SuppressionScope::File,
+                    line: 1,
+                    parameters: vec!["_a".to_string()],
+                },
+                Suppression {
+                    scope: SuppressionScope::NextLine,
+                    line: 2,
+                    parameters: vec!["_b".to_string(), "_c".to_string()],
+                },
+                Suppression {
+                    scope: SuppressionScope::Line,
+                    line: 5,
+                    parameters: Vec::new(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_covers_matches_line_and_parameter() {
+        let next_line = Suppression {
+            scope: SuppressionScope::NextLine,
+            line: 4,
+            parameters: vec!["_a".to_string()],
+        };
+
+        assert!(next_line.covers(&finding("_a", 5), 5));
+        assert!(next_line.covers(&finding("_a", 7), 5));
+        assert!(!next_line.covers(&finding("_b", 5), 5));
+        assert!(!next_line.covers(&finding("_a", 6), 6));
+    }
+}
@@ -1,10 +1,15 @@
 //! Types and data structures used throughout the application
 
+use std::collections::BTreeMap;
 use std::error::Error;
 use std::fmt;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
+use crate::fingerprint;
+use crate::severity::{Severity, SeverityRule};
+
 /// Custom error type for the application
 #[derive(Debug)]
 pub enum ConstructorAnalyzerError {
@@ -16,6 +21,8 @@ pub enum ConstructorAnalyzerError {
     NotFound(String),
     /// Invalid file format
     InvalidFormat(String),
+    /// Invalid include or exclude glob
+    GlobError(globset::Error),
 }
 
 impl fmt::Display for ConstructorAnalyzerError {
@@ -25,6 +32,7 @@ impl fmt::Display for ConstructorAnalyzerError {
             ConstructorAnalyzerError::RegexError(err) => write!(f, "Regex error: {err}"),
             ConstructorAnalyzerError::NotFound(path) => write!(f, "Path not found: {path}"),
             ConstructorAnalyzerError::InvalidFormat(msg) => write!(f, "Invalid format: {msg}"),
+            ConstructorAnalyzerError::GlobError(err) => write!(f, "Glob error: {err}"),
         }
     }
 }
@@ -37,6 +45,12 @@ impl From<std::io::Error> for ConstructorAnalyzerError {
     }
 }
 
+impl From<globset::Error> for ConstructorAnalyzerError {
+    fn from(err: globset::Error) -> Self {
+        ConstructorAnalyzerError::GlobError(err)
+    }
+}
+
 impl From<regex::Error> for ConstructorAnalyzerError {
     fn from(err: regex::Error) -> Self {
         ConstructorAnalyzerError::RegexError(err)
@@ -50,6 +64,24 @@ pub struct AnalysisResult {
     pub function_type: FunctionType,
     /// File name where the function was found
     pub file_name: String,
+    /// Path of the file, as reached from the analyzed path
+    pub file_path: String,
+    /// 1-based line where the function starts
+    pub line: usize,
+    /// 1-based column where the function starts
+    pub column: usize,
+    /// Contract, library or interface enclosing the function, if any
+    pub contract_name: Option<String>,
+    /// Declared visibility, or the implicit one for free functions
+    pub visibility: Option<Visibility>,
+    /// Declared state mutability
+    pub mutability: StateMutability,
+    /// Modifier invocations in the header, with their arguments
+    pub modifiers: Vec<String>,
+    /// Whether only a trusted caller can reach the function
+    pub trusted_caller: bool,
+    /// Access-control modifiers and caller checks that make the caller trusted
+    pub access_controls: Vec<String>,
     /// Function arguments
     pub arguments: String,
     /// Function body code
@@ -58,10 +90,23 @@ pub struct AnalysisResult {
     pub address_arguments: Vec<(String, String)>,
     /// Variables that have zero address validation
     pub validated_variables: Vec<String>,
+    /// Where and how each validated variable is checked
+    pub validation_evidence: Vec<ValidationEvidence>,
     /// Variables that are missing zero address validation
     pub missing_validations: Vec<String>,
+    /// Unchecked parameters whose NatSpec documents that they may be `address(0)`
+    pub zero_address_allowed: Vec<String>,
     /// Types of validation found
     pub validation_types: Vec<ValidationType>,
+    /// Missing validations with their severity
+    pub findings: Vec<Finding>,
+    /// Missing validations accepted by a `construstor-disable` comment; their parameters
+    /// are not in `missing_validations`
+    pub suppressed_findings: Vec<Finding>,
+    /// Original source of the function, when the analyzer was asked to keep it
+    pub source: Option<SourceExcerpt>,
+    /// Whether the file is first-party code, a dependency or a test or script
+    pub origin: FileOrigin,
 }
 
 /// Represents the result of analyzing a function for JSON output (without code field)
@@ -71,16 +116,48 @@ pub struct AnalysisResultJson {
     pub function_type: FunctionType,
     /// File name where the function was found
     pub file_name: String,
+    /// Path of the file, as reached from the analyzed path
+    pub file_path: String,
+    /// 1-based line where the function starts
+    pub line: usize,
+    /// 1-based column where the function starts
+    pub column: usize,
+    /// Contract, library or interface enclosing the function, if any
+    pub contract_name: Option<String>,
+    /// Whether the file is first-party code, a dependency or a test or script
+    pub origin: FileOrigin,
+    /// Declared visibility, or the implicit one for free functions
+    pub visibility: Option<Visibility>,
+    /// Declared state mutability
+    pub mutability: StateMutability,
+    /// Modifier invocations in the header, with their arguments
+    pub modifiers: Vec<String>,
+    /// Whether only a trusted caller can reach the function
+    pub trusted_caller: bool,
+    /// Access-control modifiers and caller checks that make the caller trusted
+    pub access_controls: Vec<String>,
     /// Function arguments
     pub arguments: String,
     /// Address arguments found in the function signature
     pub address_arguments: Vec<String>,
     /// Variables that have zero address validation
     pub validated_variables: Vec<String>,
+    /// Where and how each validated variable is checked
+    pub validation_evidence: Vec<ValidationEvidence>,
     /// Variables that are missing zero address validation
     pub missing_validations: Vec<String>,
+    /// Unchecked parameters whose NatSpec documents that they may be `address(0)`
+    pub zero_address_allowed: Vec<String>,
     /// Types of validation found
     pub validation_types: Vec<ValidationType>,
+    /// Missing validations with their severity
+    pub findings: Vec<Finding>,
+    /// Missing validations accepted by a `construstor-disable` comment; their parameters
+    /// are not in `missing_validations`
+    pub suppressed_findings: Vec<Finding>,
+    /// Original source of the function, only with `--json-include-code`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<SourceExcerpt>,
 }
 
 impl From<&AnalysisResult> for AnalysisResultJson {
@@ -88,6 +165,16 @@ impl From<&AnalysisResult> for AnalysisResultJson {
         AnalysisResultJson {
             function_type: result.function_type.clone(),
             file_name: result.file_name.clone(),
+            file_path: result.file_path.clone(),
+            line: result.line,
+            column: result.column,
+            contract_name: result.contract_name.clone(),
+            origin: result.origin,
+            visibility: result.visibility,
+            mutability: result.mutability,
+            modifiers: result.modifiers.clone(),
+            trusted_caller: result.trusted_caller,
+            access_controls: result.access_controls.clone(),
             arguments: result.arguments.clone(),
             address_arguments: result
                 .address_arguments
@@ -95,9 +182,283 @@ impl From<&AnalysisResult> for AnalysisResultJson {
                 .map(|(_, name)| name.clone())
                 .collect(),
             validated_variables: result.validated_variables.clone(),
+            validation_evidence: result.validation_evidence.clone(),
             missing_validations: result.missing_validations.clone(),
+            zero_address_allowed: result.zero_address_allowed.clone(),
             validation_types: result.validation_types.clone(),
+            findings: result.findings.clone(),
+            suppressed_findings: result.suppressed_findings.clone(),
+            source: result.source.clone(),
+        }
+    }
+}
+
+/// The original source of a function with a few surrounding lines
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceExcerpt {
+    /// Source text from the start of the definition to its closing brace
+    pub code: String,
+    /// 1-based line on which `code` starts
+    pub start_line: usize,
+    /// Lines immediately before the definition
+    pub context_before: Vec<String>,
+    /// Lines immediately after the definition
+    pub context_after: Vec<String>,
+    /// Where the unvalidated parameters are declared in `code`
+    pub unvalidated_parameters: Vec<ParameterSpan>,
+}
+
+/// Byte range of a parameter name within a [`SourceExcerpt`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParameterSpan {
+    /// Name of the parameter
+    pub parameter: String,
+    /// Byte offset of the first character of the name
+    pub start: usize,
+    /// Byte offset just past the name
+    pub end: usize,
+}
+
+/// A zero address check found for a variable
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationEvidence {
+    /// Variable being checked
+    pub variable: String,
+    /// Kind of check
+    pub kind: ValidationType,
+    /// Source text of the check
+    pub source: String,
+    /// 1-based line of the check in the file
+    pub line: usize,
+    /// 1-based column of the check in the file
+    pub column: usize,
+}
+
+/// A missing zero address validation with the severity assigned to it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Finding {
+    /// Identifies the finding across runs, see [`fingerprint`](crate::fingerprint)
+    pub fingerprint: String,
+    /// Name of the unvalidated parameter
+    pub parameter: String,
+    /// 1-based line of the parameter declaration
+    pub line: usize,
+    /// 1-based column of the parameter declaration
+    pub column: usize,
+    /// Severity assigned by the matching rule
+    pub severity: Severity,
+    /// Rule that decided the severity
+    pub rule: SeverityRule,
+    /// How the parameter is used in the function body
+    pub usage: Vec<ParameterUsage>,
+}
+
+/// How an unvalidated address parameter is used in the function body
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParameterUsage {
+    /// Assigned to a mutable state variable or pushed to a state array
+    StoredToState,
+    /// Assigned to an immutable state variable
+    StoredToImmutable,
+    /// Target of a low-level call or an interface call
+    CallTarget,
+    /// Target of a delegatecall
+    DelegatecallTarget,
+    /// Receives tokens or ether
+    TransferRecipient,
+    /// Only used as an event argument
+    EmittedOnly,
+    /// Passed as an argument to another function
+    PassedToFunction,
+    /// Used in some other way, such as a comparison or a local variable
+    Other,
+    /// Never referenced in the body
+    Unused,
+}
+
+impl ParameterUsage {
+    /// Returns a short human-readable description
+    pub fn description(&self) -> &'static str {
+        match self {
+            ParameterUsage::StoredToState => "stored to state",
+            ParameterUsage::StoredToImmutable => "stored to immutable",
+            ParameterUsage::CallTarget => "call target",
+            ParameterUsage::DelegatecallTarget => "delegatecall target",
+            ParameterUsage::TransferRecipient => "transfer recipient",
+            ParameterUsage::EmittedOnly => "emitted only",
+            ParameterUsage::PassedToFunction => "passed to another function",
+            ParameterUsage::Other => "other use",
+            ParameterUsage::Unused => "unused",
+        }
+    }
+}
+
+impl AnalysisResult {
+    /// Returns the highest severity among the findings, if any
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.findings.iter().map(|finding| finding.severity).max()
+    }
+
+    /// Returns the function name qualified by its contract, e.g. `Vault.setOwner`
+    pub fn qualified_name(&self) -> String {
+        match &self.contract_name {
+            Some(contract) => format!("{}.{}", contract, self.function_type.name()),
+            None => self.function_type.name().to_string(),
+        }
+    }
+
+    /// Returns the function name with its parameter types, e.g. `setPool(address,uint256)`
+    pub fn canonical_signature(&self) -> String {
+        fingerprint::canonical_signature(self.function_type.name(), &self.arguments)
+    }
+
+    /// Moves the findings that `accepted` selects out of the result and returns them
+    ///
+    /// Their parameters leave `missing_validations` as well, so that summaries and reports
+    /// only count the findings that remain.
+    pub fn take_findings(&mut self, mut accepted: impl FnMut(&Finding) -> bool) -> Vec<Finding> {
+        let (taken, kept): (Vec<Finding>, Vec<Finding>) = std::mem::take(&mut self.findings)
+            .into_iter()
+            .partition(|finding| accepted(finding));
+        self.missing_validations
+            .retain(|parameter| !taken.iter().any(|finding| finding.parameter == *parameter));
+        self.findings = kept;
+        taken
+    }
+
+    /// Describes a finding of this result in one sentence
+    pub fn finding_message(&self, finding: &Finding) -> String {
+        let location = match &self.contract_name {
+            Some(contract) => format!(" in {contract}"),
+            None => String::new(),
+        };
+        format!(
+            "Parameter '{}' of {}{} is not checked against address(0)",
+            finding.parameter, self.function_type, location
+        )
+    }
+}
+
+/// A file that could not be analyzed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileError {
+    /// Path of the file
+    pub path: String,
+    /// Why it could not be analyzed
+    pub message: String,
+}
+
+/// Results of analyzing a file or directory, with the files that could not be analyzed
+#[derive(Debug, Clone, Default)]
+pub struct PathAnalysis {
+    /// Results of every analyzed file
+    pub results: Vec<AnalysisResult>,
+    /// Files that could not be read
+    pub errors: Vec<FileError>,
+}
+
+/// Validation counts over a set of functions with address arguments
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValidationCounts {
+    /// Functions with at least one address argument
+    pub with_address_arguments: usize,
+    /// Functions with no missing validation
+    pub fully_validated: usize,
+    /// Functions with some, but not all, address arguments validated
+    pub partially_validated: usize,
+    /// Functions with no validated variable at all
+    pub not_validated: usize,
+}
+
+impl ValidationCounts {
+    fn count(results: &[&AnalysisResult]) -> Self {
+        let with_address_args: Vec<_> = results
+            .iter()
+            .filter(|r| !r.address_arguments.is_empty())
+            .collect();
+
+        ValidationCounts {
+            with_address_arguments: with_address_args.len(),
+            fully_validated: with_address_args
+                .iter()
+                .filter(|r| r.missing_validations.is_empty())
+                .count(),
+            partially_validated: with_address_args
+                .iter()
+                .filter(|r| !r.missing_validations.is_empty() && !r.validated_variables.is_empty())
+                .count(),
+            not_validated: with_address_args
+                .iter()
+                .filter(|r| !r.missing_validations.is_empty() && r.validated_variables.is_empty())
+                .count(),
+        }
+    }
+
+    fn merge(&mut self, other: &ValidationCounts) {
+        self.with_address_arguments += other.with_address_arguments;
+        self.fully_validated += other.fully_validated;
+        self.partially_validated += other.partially_validated;
+        self.not_validated += other.not_validated;
+    }
+}
+
+/// Summary statistics over a set of analysis results
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Summary {
+    /// Number of functions analyzed
+    pub total_functions: usize,
+    /// Counts over every function
+    #[serde(flatten)]
+    pub overall: ValidationCounts,
+    /// Counts over functions only a trusted caller can reach
+    pub trusted_caller: ValidationCounts,
+    /// Counts over functions anyone can reach
+    pub permissionless: ValidationCounts,
+    /// Counts over the functions of each origin present in the results
+    pub by_origin: BTreeMap<FileOrigin, ValidationCounts>,
+    /// Number of findings accepted by a suppression comment
+    pub suppressed_findings: usize,
+}
+
+impl Summary {
+    /// Computes summary statistics for the given results
+    pub fn from_results(results: &[AnalysisResult]) -> Self {
+        let all: Vec<_> = results.iter().collect();
+        let (trusted, permissionless): (Vec<_>, Vec<_>) =
+            all.iter().partition(|r| r.trusted_caller);
+
+        let mut origins: Vec<FileOrigin> = results.iter().map(|r| r.origin).collect();
+        origins.sort();
+        origins.dedup();
+        let by_origin = origins
+            .into_iter()
+            .map(|origin| {
+                let of_origin: Vec<_> =
+                    all.iter().copied().filter(|r| r.origin == origin).collect();
+                (origin, ValidationCounts::count(&of_origin))
+            })
+            .collect();
+
+        Summary {
+            total_functions: results.len(),
+            overall: ValidationCounts::count(&all),
+            trusted_caller: ValidationCounts::count(&trusted),
+            permissionless: ValidationCounts::count(&permissionless),
+            by_origin,
+            suppressed_findings: results.iter().map(|r| r.suppressed_findings.len()).sum(),
+        }
+    }
+
+    /// Adds the counts of another summary, e.g. to total results that arrive file by file
+    pub fn merge(&mut self, other: &Summary) {
+        self.total_functions += other.total_functions;
+        self.overall.merge(&other.overall);
+        self.trusted_caller.merge(&other.trusted_caller);
+        self.permissionless.merge(&other.permissionless);
+        for (origin, counts) in &other.by_origin {
+            self.by_origin.entry(*origin).or_default().merge(counts);
         }
+        self.suppressed_findings += other.suppressed_findings;
     }
 }
 
@@ -107,6 +468,183 @@ pub enum FunctionType {
     Constructor,
     Initialize,
     Regular(String), // Function name for regular functions
+    Modifier(String),
+    FreeFunction(String),    // Function declared at file level
+    LibraryFunction(String), // Function declared inside a library
+    Fallback,
+    Receive,
+}
+
+impl FunctionType {
+    /// Names accepted by the `--kind` filter, one per variant
+    pub const KINDS: [&'static str; 8] = [
+        "constructor",
+        "initialize",
+        "function",
+        "modifier",
+        "free-function",
+        "library-function",
+        "fallback",
+        "receive",
+    ];
+
+    /// Returns the name of the function, or the keyword for unnamed ones
+    pub fn name(&self) -> &str {
+        match self {
+            FunctionType::Constructor => "constructor",
+            FunctionType::Initialize => "initialize",
+            FunctionType::Fallback => "fallback",
+            FunctionType::Receive => "receive",
+            FunctionType::Regular(name)
+            | FunctionType::Modifier(name)
+            | FunctionType::FreeFunction(name)
+            | FunctionType::LibraryFunction(name) => name,
+        }
+    }
+
+    /// Returns the kind name used for filtering
+    pub fn kind(&self) -> &'static str {
+        match self {
+            FunctionType::Constructor => "constructor",
+            FunctionType::Initialize => "initialize",
+            FunctionType::Regular(_) => "function",
+            FunctionType::Modifier(_) => "modifier",
+            FunctionType::FreeFunction(_) => "free-function",
+            FunctionType::LibraryFunction(_) => "library-function",
+            FunctionType::Fallback => "fallback",
+            FunctionType::Receive => "receive",
+        }
+    }
+}
+
+/// Function visibility
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Visibility {
+    External,
+    Public,
+    Internal,
+    Private,
+}
+
+impl Visibility {
+    /// Names accepted by the `--visibility` filter, one per variant
+    pub const NAMES: [&'static str; 4] = ["external", "public", "internal", "private"];
+
+    /// Returns the Solidity keyword for this visibility
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Visibility::External => "external",
+            Visibility::Public => "public",
+            Visibility::Internal => "internal",
+            Visibility::Private => "private",
+        }
+    }
+}
+
+/// Where an analyzed file comes from
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum FileOrigin {
+    /// The project's own contracts
+    #[default]
+    FirstParty,
+    /// Vendored or installed libraries: `lib/`, `node_modules/` and remapping targets
+    Dependency,
+    /// Foundry tests and scripts: `*.t.sol`, `*.s.sol` and the `test/`, `tests/` and
+    /// `script/` directories
+    TestScript,
+}
+
+impl FileOrigin {
+    /// Names accepted by the `--origin` filter, one per variant
+    pub const NAMES: [&'static str; 3] = ["first-party", "dependency", "test-script"];
+
+    /// Returns the name of this origin
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FileOrigin::FirstParty => "first-party",
+            FileOrigin::Dependency => "dependency",
+            FileOrigin::TestScript => "test-script",
+        }
+    }
+
+    /// Returns a capitalized label for summaries
+    pub fn label(&self) -> &'static str {
+        match self {
+            FileOrigin::FirstParty => "First-party",
+            FileOrigin::Dependency => "Dependency",
+            FileOrigin::TestScript => "Test/script",
+        }
+    }
+
+    /// Classifies a file from its path relative to the analyzed directory, given the
+    /// directories that remappings point to, relative to the same directory
+    pub fn classify(relative: &Path, dependency_roots: &[PathBuf]) -> Self {
+        let file_name = relative
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+        let directories: Vec<_> = relative
+            .parent()
+            .map(|parent| parent.iter().collect())
+            .unwrap_or_default();
+        let first = directories.first();
+
+        if directories.iter().any(|dir| *dir == "node_modules")
+            || first.is_some_and(|dir| *dir == "lib")
+            || dependency_roots
+                .iter()
+                .any(|root| relative.starts_with(root))
+        {
+            FileOrigin::Dependency
+        } else if file_name.ends_with(".t.sol")
+            || file_name.ends_with(".s.sol")
+            || first.is_some_and(|dir| *dir == "test" || *dir == "tests" || *dir == "script")
+        {
+            FileOrigin::TestScript
+        } else {
+            FileOrigin::FirstParty
+        }
+    }
+}
+
+/// Function state mutability
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum StateMutability {
+    Pure,
+    View,
+    Payable,
+    #[default]
+    NonPayable,
+}
+
+impl StateMutability {
+    /// Returns the Solidity keyword for this mutability, if it has one
+    pub fn keyword(&self) -> Option<&'static str> {
+        match self {
+            StateMutability::Pure => Some("pure"),
+            StateMutability::View => Some("view"),
+            StateMutability::Payable => Some("payable"),
+            StateMutability::NonPayable => None,
+        }
+    }
+}
+
+impl fmt::Display for FunctionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FunctionType::Constructor => f.write_str("constructor"),
+            FunctionType::Initialize => f.write_str("initialize function"),
+            FunctionType::Regular(name) => write!(f, "function '{name}'"),
+            FunctionType::Modifier(name) => write!(f, "modifier '{name}'"),
+            FunctionType::FreeFunction(name) => write!(f, "free function '{name}'"),
+            FunctionType::LibraryFunction(name) => write!(f, "library function '{name}'"),
+            FunctionType::Fallback => f.write_str("fallback function"),
+            FunctionType::Receive => f.write_str("receive function"),
+        }
+    }
 }
 
 /// Type of zero address validation found
@@ -114,4 +652,5 @@ pub enum FunctionType {
 pub enum ValidationType {
     EqualityCheck,
     RequireStatement,
+    ValidatorCall,
 }